@@ -0,0 +1,159 @@
+use std::fmt::Display;
+
+use nom::{
+    combinator::{all_consuming, cut},
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    multi::separated_list1,
+    Finish, IResult, Parser,
+};
+use thiserror::Error;
+
+use crate::position;
+
+/// Result of a nom parser that keeps enough context to build a [`Diagnostic`].
+pub type ParseResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// Matches a literal, naming it in backticks if it is missing, e.g. "expected `Monkey`".
+#[macro_export]
+macro_rules! literal {
+    ($literal:literal) => {
+        ::nom::error::context(
+            concat!("`", $literal, "`"),
+            ::nom::bytes::complete::tag($literal),
+        )
+    };
+}
+
+/// A parse error pointing at the offending character of the puzzle input.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub col: usize,
+    pub expected: String,
+    pub source_line: String,
+}
+
+impl Diagnostic {
+    pub fn new(input: &str, error: VerboseError<&str>) -> Self {
+        // The first entry is where parsing actually failed, the rest are the constructs that
+        // were being parsed around it, innermost first.
+        let remaining = error
+            .errors
+            .first()
+            .map_or(&input[input.len()..], |(remaining, _)| *remaining);
+        let expected = error
+            .errors
+            .iter()
+            .find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(context.to_string()),
+                _ => None,
+            })
+            .or_else(|| {
+                error.errors.first().map(|(_, kind)| match kind {
+                    VerboseErrorKind::Char(ch) => format!("`{ch}`"),
+                    VerboseErrorKind::Nom(kind) => describe(*kind),
+                    VerboseErrorKind::Context(context) => context.to_string(),
+                })
+            })
+            .unwrap_or_else(|| "valid input".to_string());
+
+        let (line, col) = position(input, remaining);
+        let source_line = input.lines().nth(line - 1).unwrap_or_default().to_string();
+        Self {
+            line,
+            col,
+            expected,
+            source_line,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.col, self.expected
+        )?;
+        writeln!(f, "{}", self.source_line)?;
+        write!(f, "{:>width$}", "^", width = self.col)
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "a number".to_string(),
+        ErrorKind::Alpha => "a letter".to_string(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".to_string(),
+        ErrorKind::CrLf => "a newline".to_string(),
+        ErrorKind::Eof => "end of input".to_string(),
+        kind => kind.description().to_lowercase(),
+    }
+}
+
+/// Runs `parser` over the whole of `input`, which may end with trailing whitespace.
+pub fn parse<'a, T>(
+    input: &'a str,
+    parser: impl Parser<&'a str, T, VerboseError<&'a str>>,
+) -> Result<T, Diagnostic> {
+    all_consuming(parser)(input.trim_end())
+        .finish()
+        .map(|(_, parsed)| parsed)
+        .map_err(|e| Diagnostic::new(input, e))
+}
+
+/// Parses `input` as one or more `item`s separated by `separator`. Once a separator has matched,
+/// the next item must parse, so the diagnostic points into the bad item rather than at the
+/// separator before it.
+pub fn parse_all<'a, T, S>(
+    input: &'a str,
+    separator: impl Parser<&'a str, S, VerboseError<&'a str>>,
+    item: impl Parser<&'a str, T, VerboseError<&'a str>>,
+) -> Result<Vec<T>, Diagnostic> {
+    parse(input, separated_list1(separator, cut(item)))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{character::complete::newline, sequence::preceded};
+
+    use super::*;
+
+    fn valve(input: &str) -> ParseResult<'_, &str> {
+        preceded(
+            literal!("Valve "),
+            preceded(
+                literal!("tunnel leads to valve "),
+                nom::character::complete::alpha1,
+            ),
+        )(input)
+    }
+
+    #[test]
+    fn diagnostic() {
+        let input = "Valve tunnel leads to valve AA\nValve tunnels lead to valve BB\n";
+        let diagnostic = parse_all(input, newline, valve).unwrap_err();
+        assert_eq!(
+            Diagnostic {
+                line: 2,
+                col: 7,
+                expected: "`tunnel leads to valve `".into(),
+                source_line: "Valve tunnels lead to valve BB".into(),
+            },
+            diagnostic
+        );
+        assert_eq!(
+            "line 2, column 7: expected `tunnel leads to valve `\n\
+             Valve tunnels lead to valve BB\n      ^",
+            diagnostic.to_string()
+        );
+    }
+
+    #[test]
+    fn trailing_newline() {
+        assert_eq!(
+            Ok(vec!["AA"]),
+            parse_all("Valve tunnel leads to valve AA\n", newline, valve)
+        );
+    }
+}
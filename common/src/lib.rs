@@ -0,0 +1,53 @@
+use std::{
+    fmt::Display,
+    io::{self, Read},
+};
+
+use anyhow::{Context, Result};
+use thiserror::Error;
+
+pub mod diagnostic;
+
+/// Returned by a [`Solver`] part that has no solution yet, so runners can skip it rather than
+/// count it as a failure.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+#[error("not solved yet")]
+pub struct Unsolved;
+
+/// A day's puzzle, solvable in two parts from the raw puzzle input.
+pub trait Solver {
+    type Part1: Display;
+    type Part2: Display;
+
+    fn part1(input: &str) -> Result<Self::Part1>;
+    fn part2(input: &str) -> Result<Self::Part2>;
+}
+
+/// 1-based line and column at which `remaining` starts within `input`.
+///
+/// `remaining` must be a subslice of `input`, such as what's left of it after parsing a prefix,
+/// because the position is worked out from where the two point in memory. A copy with the same
+/// text won't do. Debug builds panic if `remaining` lies outside `input`.
+pub fn position(input: &str, remaining: &str) -> (usize, usize) {
+    let input_range = input.as_bytes().as_ptr_range();
+    let remaining_range = remaining.as_bytes().as_ptr_range();
+    debug_assert!(
+        input_range.start <= remaining_range.start && remaining_range.end <= input_range.end,
+        "`remaining` must be a subslice of `input`"
+    );
+    let offset = (remaining.as_ptr() as usize)
+        .saturating_sub(input.as_ptr() as usize)
+        .min(input.len());
+    let consumed = &input[..offset];
+    let line = consumed.matches('\n').count() + 1;
+    let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+    let col = consumed[line_start..].chars().count() + 1;
+    (line, col)
+}
+
+pub fn read_stdin() -> Result<String> {
+    let mut buffer = String::new();
+    let mut stdin = io::stdin().lock();
+    stdin.read_to_string(&mut buffer).context("read stdin")?;
+    Ok(buffer)
+}
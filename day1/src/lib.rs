@@ -0,0 +1,306 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    io::{self, BufRead},
+};
+
+use common::Solver;
+use thiserror::Error;
+
+pub mod statistics;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("line {line}: {token:?} is not a valid number of calories")]
+    InvalidCalories { token: String, line: usize },
+    #[error("input does not contain any elves")]
+    NoElves,
+    #[error("line {line}: failed to read input: {kind}")]
+    Io { kind: io::ErrorKind, line: usize },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Elf {
+    /// Calories of each item the elf carries, in input order.
+    pub items: Vec<u32>,
+}
+
+impl Elf {
+    /// Total calories carried, which can't overflow even for very large inventories.
+    pub fn calories(&self) -> u64 {
+        self.items.iter().map(|&calories| u64::from(calories)).sum()
+    }
+
+    /// Reads elves one calorie group at a time, so only the current line is held in memory.
+    pub fn iter_from_reader<R: BufRead>(reader: R) -> Elves<R> {
+        Elves {
+            lines: reader.lines(),
+            line: 0,
+        }
+    }
+}
+
+/// Iterator over the blank-line separated calorie groups of a reader.
+pub struct Elves<R> {
+    lines: io::Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<Elf, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut current_elf = None;
+        for line in self.lines.by_ref() {
+            self.line += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    return Some(Err(Error::Io {
+                        kind: e.kind(),
+                        line: self.line,
+                    }))
+                }
+            };
+            if line.is_empty() {
+                return Some(Ok(current_elf.unwrap_or_default()));
+            }
+
+            let calories = match line.parse::<u32>() {
+                Ok(calories) => calories,
+                Err(_) => {
+                    return Some(Err(Error::InvalidCalories {
+                        token: line,
+                        line: self.line,
+                    }))
+                }
+            };
+            current_elf
+                .get_or_insert_with(Elf::default)
+                .items
+                .push(calories);
+        }
+        // The last elf isn't necessarily followed by a blank line
+        current_elf.map(Ok)
+    }
+}
+
+pub fn parse_elves(input: &str) -> Result<Vec<Elf>, Error> {
+    Elf::iter_from_reader(input.as_bytes()).collect()
+}
+
+/// An elf's place among those carrying the most calories.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RankedElf {
+    /// 1-based, elves with equal totals share a rank and the next rank is skipped.
+    pub rank: usize,
+    /// 0-based position of the elf in the input.
+    pub index: usize,
+    pub calories: u64,
+    /// Whether another ranked elf carries the same total.
+    pub tied: bool,
+}
+
+/// The `k` elves carrying the most calories, best first.
+///
+/// Elves tied with the `k`th elf are all included rather than picking one arbitrarily, so more
+/// than `k` elves can be returned. Elves with equal totals are ordered by their index.
+pub fn top_k_elves(input: &str, k: usize) -> Result<Vec<RankedElf>, Error> {
+    top_k_elves_from_reader(input.as_bytes(), k)
+}
+
+pub fn top_k_elves_from_reader(reader: impl BufRead, k: usize) -> Result<Vec<RankedElf>, Error> {
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Min-heap of the best `k` elves seen so far, keyed so that among equal totals the latest
+    // elf is evicted first.
+    let mut top: BinaryHeap<Reverse<(u64, Reverse<usize>)>> = BinaryHeap::with_capacity(k + 1);
+    // Elves that didn't fit in the heap but are tied with the lowest total in it
+    let mut ties: Vec<(u64, usize)> = Vec::new();
+
+    for (index, elf) in Elf::iter_from_reader(reader).enumerate() {
+        let calories = elf?.calories();
+        top.push(Reverse((calories, Reverse(index))));
+        if top.len() <= k {
+            continue;
+        }
+
+        let Reverse((evicted, Reverse(evicted_index))) = top.pop().expect("heap holds k + 1 elves");
+        let Reverse((lowest, _)) = top.peek().expect("heap holds k elves");
+        ties.retain(|(tied, _)| tied == lowest);
+        if evicted == *lowest {
+            ties.push((evicted, evicted_index));
+        }
+    }
+
+    let mut elves: Vec<(u64, usize)> = top
+        .into_iter()
+        .map(|Reverse((calories, Reverse(index)))| (calories, index))
+        .chain(ties)
+        .collect();
+    elves.sort_by_key(|&(calories, index)| (Reverse(calories), index));
+
+    let mut ranked: Vec<RankedElf> = Vec::with_capacity(elves.len());
+    for (position, &(calories, index)) in elves.iter().enumerate() {
+        let rank = match ranked.last() {
+            Some(previous) if previous.calories == calories => previous.rank,
+            _ => position + 1,
+        };
+        let tied = elves
+            .get(position + 1)
+            .is_some_and(|(next, _)| *next == calories)
+            || ranked
+                .last()
+                .is_some_and(|previous| previous.calories == calories);
+        ranked.push(RankedElf {
+            rank,
+            index,
+            calories,
+            tied,
+        });
+    }
+    Ok(ranked)
+}
+
+fn sum_of_top(elves: &[RankedElf], n: usize) -> u64 {
+    elves.iter().take(n).map(|elf| elf.calories).sum()
+}
+
+/// Most calories carried by a single elf.
+pub fn solve_part1(input: &str) -> Result<u64, Error> {
+    let top = top_k_elves(input, 1)?;
+    top.first().map(|elf| elf.calories).ok_or(Error::NoElves)
+}
+
+/// Total calories carried by the top 3 elves.
+pub fn solve_part2(input: &str) -> Result<u64, Error> {
+    Ok(sum_of_top(&top_k_elves(input, 3)?, 3))
+}
+
+/// Both answers from a single pass over `reader`, for inputs too large to read into memory.
+pub fn solve_from_reader(reader: impl BufRead) -> Result<(u64, u64), Error> {
+    let top = top_k_elves_from_reader(reader, 3)?;
+    let most = top.first().map(|elf| elf.calories).ok_or(Error::NoElves)?;
+    Ok((most, sum_of_top(&top, 3)))
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn part1(input: &str) -> anyhow::Result<u64> {
+        Ok(solve_part1(input)?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<u64> {
+        Ok(solve_part2(input)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    #[test]
+    fn example() {
+        assert_eq!(24000, solve_part1(EXAMPLE).unwrap());
+        assert_eq!(45000, solve_part2(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn invalid_calories() {
+        assert_eq!(
+            Some(Error::InvalidCalories {
+                token: "12a".into(),
+                line: 3
+            }),
+            parse_elves("1000\n\n12a\n").err()
+        );
+    }
+
+    #[test]
+    fn last_elf_without_trailing_blank_line() {
+        assert_eq!(3, parse_elves("1000\n\n2000\n\n3000").unwrap().len());
+        assert_eq!(3, parse_elves("1000\n\n2000\n\n3000\n").unwrap().len());
+        assert_eq!(30000, solve_part1("1000\n\n30000").unwrap());
+    }
+
+    #[test]
+    fn items() {
+        let elves = parse_elves(EXAMPLE).unwrap();
+        assert_eq!(vec![1000, 2000, 3000], elves[0].items);
+        assert_eq!(6000, elves[0].calories());
+        // Totals that don't fit in a u32 are still summed correctly
+        let elves = parse_elves("4294967295\n4294967295").unwrap();
+        assert_eq!(2 * u64::from(u32::MAX), elves[0].calories());
+    }
+
+    #[test]
+    fn top_k() {
+        let elf = |rank, index, calories, tied| RankedElf {
+            rank,
+            index,
+            calories,
+            tied,
+        };
+        assert_eq!(
+            vec![elf(1, 3, 24000, false), elf(2, 2, 11000, false)],
+            top_k_elves(EXAMPLE, 2).unwrap()
+        );
+        assert_eq!(Vec::<RankedElf>::new(), top_k_elves(EXAMPLE, 0).unwrap());
+        assert_eq!(5, top_k_elves(EXAMPLE, 10).unwrap().len());
+    }
+
+    #[test]
+    fn top_k_ties() {
+        let elf = |rank, index, calories, tied| RankedElf {
+            rank,
+            index,
+            calories,
+            tied,
+        };
+        let input = "5\n\n9\n\n5\n\n7\n\n5\n\n1";
+        assert_eq!(
+            vec![
+                elf(1, 1, 9, false),
+                elf(2, 3, 7, false),
+                elf(3, 0, 5, true),
+                elf(3, 2, 5, true),
+                elf(3, 4, 5, true),
+            ],
+            top_k_elves(input, 3).unwrap()
+        );
+        assert_eq!(
+            vec![elf(1, 0, 5, true), elf(1, 1, 5, true)],
+            top_k_elves("5\n\n5\n\n3", 1).unwrap()
+        );
+        // Ties that are pushed out by a later, larger elf are dropped
+        assert_eq!(
+            vec![elf(1, 2, 8, false), elf(2, 3, 6, false)],
+            top_k_elves("5\n\n5\n\n8\n\n6", 2).unwrap()
+        );
+        assert_eq!(21, solve_part2(input).unwrap());
+    }
+
+    #[test]
+    fn from_reader() {
+        assert_eq!(Ok((24000, 45000)), solve_from_reader(EXAMPLE.as_bytes()));
+        assert_eq!(
+            Ok((24000, 45000)),
+            solve_from_reader(EXAMPLE.trim_end().as_bytes())
+        );
+        assert_eq!(
+            Some(Error::InvalidCalories {
+                token: "12a".into(),
+                line: 3
+            }),
+            Elf::iter_from_reader("1000\n\n12a\n".as_bytes()).find_map(Result::err)
+        );
+    }
+}
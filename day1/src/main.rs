@@ -1,54 +1,94 @@
-use anyhow::{Context, Result};
-use std::io::{self, Read};
+use std::{env, io};
 
-struct Elf {
-    calories: u32,
+use anyhow::{Context, Error, Result};
+use day1::{statistics::Statistics, RankedElf};
+
+const DEFAULT_TOP: usize = 3;
+const USAGE: &str = "\
+Usage:
+    day1 [--top K] [--format plain|json]
+    day1 --stats
+
+Without options, prints both puzzle answers. With options, ranks the K elves (default 3)
+carrying the most calories, including any elves tied with the last of them. --stats reports
+how calories and items are spread across the elves.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Plain,
+    Json,
 }
 
-fn main() -> Result<()> {
-    let mut buffer = String::new();
-    let stdin = io::stdin();
-    let mut handle = stdin.lock();
-    handle
-        .read_to_string(&mut buffer)
-        .context("reading stdin to end")?;
-
-    let mut elves = Vec::new();
-    let mut current_elf = Elf { calories: 0 };
-
-    for line in buffer.split("\n") {
-        if line.is_empty() {
-            elves.push(current_elf);
-            current_elf = Elf { calories: 0 };
-            continue;
+impl TryFrom<&str> for Format {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            format => Err(Error::msg(format!("{format} is not a valid format"))),
         }
+    }
+}
 
-        let calories = line.parse::<u32>().context("parsing calories as u32")?;
-        current_elf.calories += calories;
+fn print_ranking(elves: &[RankedElf], format: Format) {
+    match format {
+        Format::Plain => {
+            for elf in elves {
+                println!(
+                    "{}{}. elf {}: {} calories",
+                    if elf.tied { "=" } else { "" },
+                    elf.rank,
+                    elf.index,
+                    elf.calories
+                );
+            }
+        }
+        Format::Json => {
+            let elves: Vec<String> = elves
+                .iter()
+                .map(|elf| {
+                    format!(
+                        r#"{{"rank":{},"index":{},"calories":{},"tied":{}}}"#,
+                        elf.rank, elf.index, elf.calories, elf.tied
+                    )
+                })
+                .collect();
+            println!("[{}]", elves.join(","));
+        }
     }
+}
 
-    let mut top_3_calories = Vec::with_capacity(3);
-    for elf in elves {
-        if top_3_calories.len() < 3 {
-            top_3_calories.push(elf.calories);
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
 
-            if top_3_calories.len() == 3 {
-                top_3_calories.sort();
-            }
+    match args.as_slice() {
+        [] => {
+            let (most, top_3) = day1::solve_from_reader(io::stdin().lock())?;
 
-            continue;
+            println!("Most calories carried by a single elf: {}", most);
+            println!("Total calories carried by top 3 elves: {}", top_3);
+            return Ok(());
         }
-
-        if elf.calories > top_3_calories[2] {
-            top_3_calories[2] = elf.calories;
-            top_3_calories.sort();
-            top_3_calories.reverse();
+        ["--stats"] => {
+            println!("{}", Statistics::from_reader(io::stdin().lock())?);
+            return Ok(());
         }
+        _ => (),
     }
 
-    println!("Most calories carried by a single elf: {}", top_3_calories[0]);
+    let mut top = DEFAULT_TOP;
+    let mut format = Format::Plain;
+    for option in args.chunks(2) {
+        match option {
+            ["--top", k] => top = k.parse().with_context(|| format!("{k} is not a valid K"))?,
+            ["--format", value] => format = Format::try_from(*value)?,
+            _ => return Err(Error::msg(USAGE)),
+        }
+    }
 
-    let total: u32 = top_3_calories.iter().sum();
-    println!("Total calories carried by top 3 elves: {total}");
+    let elves = day1::top_k_elves_from_reader(io::stdin().lock(), top)?;
+    print_ranking(&elves, format);
     Ok(())
 }
@@ -0,0 +1,220 @@
+use std::{fmt::Display, io::BufRead};
+
+use crate::{Elf, Error};
+
+const REPORT_PERCENTILES: [f64; 5] = [25.0, 50.0, 75.0, 90.0, 99.0];
+const REPORT_BUCKETS: usize = 10;
+
+/// Summary of how calories and items are spread across the elves.
+///
+/// Only each elf's total and item count are kept, so it can be built from a reader without holding
+/// every item in memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    /// Calories carried by each elf, in input order.
+    totals: Vec<u64>,
+    /// Number of items carried by each elf, in input order.
+    item_counts: Vec<usize>,
+    /// `totals` in ascending order, for the median and percentiles.
+    sorted_totals: Vec<u64>,
+}
+
+/// Elves whose totals fall within `start..=end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: u64,
+    pub end: u64,
+    pub count: usize,
+}
+
+impl Statistics {
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, Error> {
+        Elf::iter_from_reader(reader).collect()
+    }
+
+    pub fn elves(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn total_items(&self) -> usize {
+        self.item_counts.iter().sum()
+    }
+
+    pub fn total_calories(&self) -> u64 {
+        self.totals.iter().sum()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.totals.is_empty() {
+            return None;
+        }
+        Some(self.total_calories() as f64 / self.elves() as f64)
+    }
+
+    /// Middle total, or the mean of the two middle totals for an even number of elves.
+    pub fn median(&self) -> Option<f64> {
+        let n = self.sorted_totals.len();
+        if n == 0 {
+            return None;
+        }
+        let upper = self.sorted_totals[n / 2] as f64;
+        if n % 2 == 1 {
+            return Some(upper);
+        }
+        let lower = self.sorted_totals[n / 2 - 1] as f64;
+        Some((lower + upper) / 2.0)
+    }
+
+    /// Smallest total that at least `percentile`% of elves carry no more than (nearest-rank).
+    pub fn percentile(&self, percentile: f64) -> Option<u64> {
+        if self.sorted_totals.is_empty() || !(0.0..=100.0).contains(&percentile) {
+            return None;
+        }
+        let rank = (percentile / 100.0 * self.sorted_totals.len() as f64).ceil() as usize;
+        Some(self.sorted_totals[rank.max(1) - 1])
+    }
+
+    /// Splits the range from the lowest to the highest total into `buckets` equally wide buckets.
+    pub fn histogram(&self, buckets: usize) -> Vec<Bucket> {
+        let (Some(&min), Some(&max)) = (self.sorted_totals.first(), self.sorted_totals.last())
+        else {
+            return Vec::new();
+        };
+        if buckets == 0 {
+            return Vec::new();
+        }
+
+        let width = (max - min) / buckets as u64 + 1;
+        let mut histogram: Vec<Bucket> = (0..buckets as u64)
+            .map(|i| Bucket {
+                start: min + i * width,
+                end: min + (i + 1) * width - 1,
+                count: 0,
+            })
+            .take_while(|bucket| bucket.start <= max)
+            .collect();
+        for total in &self.sorted_totals {
+            histogram[((total - min) / width) as usize].count += 1;
+        }
+        histogram
+    }
+
+    /// Indices of the elves carrying the most items, all of them if several are tied.
+    pub fn most_items(&self) -> Vec<usize> {
+        let Some(&most) = self.item_counts.iter().max() else {
+            return Vec::new();
+        };
+        self.item_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count == most)
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+impl FromIterator<Elf> for Statistics {
+    fn from_iter<T: IntoIterator<Item = Elf>>(elves: T) -> Self {
+        let (totals, item_counts): (Vec<u64>, Vec<usize>) = elves
+            .into_iter()
+            .map(|elf| (elf.calories(), elf.items.len()))
+            .unzip();
+        let mut sorted_totals = totals.clone();
+        sorted_totals.sort_unstable();
+        Self {
+            totals,
+            item_counts,
+            sorted_totals,
+        }
+    }
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Elves: {}", self.elves())?;
+        writeln!(f, "Items: {}", self.total_items())?;
+        writeln!(f, "Total calories: {}", self.total_calories())?;
+        let (Some(mean), Some(median)) = (self.mean(), self.median()) else {
+            return Ok(());
+        };
+        writeln!(f, "Mean calories: {mean:.2}")?;
+        writeln!(f, "Median calories: {median:.1}")?;
+        for percentile in REPORT_PERCENTILES {
+            if let Some(total) = self.percentile(percentile) {
+                writeln!(f, "p{percentile}: {total}")?;
+            }
+        }
+        writeln!(f, "Histogram:")?;
+        for bucket in self.histogram(REPORT_BUCKETS) {
+            writeln!(
+                f,
+                "  {:>8}-{:<8} {}",
+                bucket.start, bucket.end, bucket.count
+            )?;
+        }
+        let most_items = self.most_items();
+        let items = self.item_counts[most_items[0]];
+        let elves: Vec<String> = most_items.iter().map(usize::to_string).collect();
+        write!(
+            f,
+            "Most items ({items}) carried by elf {}",
+            elves.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_elves;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    fn example() -> Statistics {
+        parse_elves(EXAMPLE).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn summary() {
+        let statistics = example();
+        assert_eq!(5, statistics.elves());
+        assert_eq!(10, statistics.total_items());
+        assert_eq!(55000, statistics.total_calories());
+        assert_eq!(Some(11000.0), statistics.mean());
+        assert_eq!(Some(10000.0), statistics.median());
+        assert_eq!(vec![0, 3], statistics.most_items());
+    }
+
+    #[test]
+    fn percentiles() {
+        let statistics = example();
+        assert_eq!(Some(4000), statistics.percentile(0.0));
+        assert_eq!(Some(6000), statistics.percentile(25.0));
+        assert_eq!(Some(10000), statistics.percentile(50.0));
+        assert_eq!(Some(24000), statistics.percentile(100.0));
+        assert_eq!(None, statistics.percentile(101.0));
+        assert_eq!(None, Statistics::from_iter(Vec::new()).percentile(50.0));
+    }
+
+    #[test]
+    fn histogram() {
+        let statistics = example();
+        assert_eq!(
+            vec![
+                Bucket {
+                    start: 4000,
+                    end: 14000,
+                    count: 4
+                },
+                Bucket {
+                    start: 14001,
+                    end: 24001,
+                    count: 1
+                },
+            ],
+            statistics.histogram(2)
+        );
+        let total: usize = statistics.histogram(7).iter().map(|b| b.count).sum();
+        assert_eq!(5, total);
+    }
+}
@@ -1,15 +1,30 @@
 use std::fmt::Display;
 
-use anyhow::{Context, Result};
+use common::{
+    diagnostic::{self, Diagnostic, ParseResult},
+    Solver,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{i32, space1},
-    combinator::all_consuming,
+    character::complete::{i32, newline, space1},
     combinator::map,
+    error::context,
     sequence::separated_pair,
-    IResult,
 };
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error(transparent)]
+    Syntax(#[from] Diagnostic),
+    #[error("pixel ({x}, {y}) is outside the screen")]
+    PixelOutOfBounds { x: usize, y: usize },
+}
+
+const NUM_SAMPLES: usize = 6;
+const WIDTH: usize = 40;
+const HEIGHT: usize = 6;
 
 #[derive(Debug)]
 pub enum Instruction {
@@ -25,20 +40,26 @@ impl Instruction {
         }
     }
 
-    pub fn parse(i: &str) -> Result<Self> {
-        all_consuming(alt((Self::noop_parser, Self::addx_parser)))(i)
-            .map(|(_, instruction)| instruction)
-            .map_err(|e| e.to_owned().into())
+    pub fn parse(i: &str) -> Result<Self, Error> {
+        Ok(diagnostic::parse(i, Self::parser)?)
     }
 
-    fn noop_parser(i: &str) -> IResult<&str, Self> {
+    fn parser(i: &str) -> ParseResult<'_, Self> {
+        context(
+            "`noop` or `addx`",
+            alt((Self::noop_parser, Self::addx_parser)),
+        )(i)
+    }
+
+    fn noop_parser(i: &str) -> ParseResult<'_, Self> {
         map(tag("noop"), |_| Self::Noop)(i)
     }
 
-    fn addx_parser(i: &str) -> IResult<&str, Self> {
-        map(separated_pair(tag("addx"), space1, i32), |(_, digit)| {
-            Self::Addx(digit)
-        })(i)
+    fn addx_parser(i: &str) -> ParseResult<'_, Self> {
+        map(
+            separated_pair(tag("addx"), space1, context("a number", i32)),
+            |(_, digit)| Self::Addx(digit),
+        )(i)
     }
 }
 
@@ -55,13 +76,12 @@ impl Screen {
         Self(vec![vec![Pixel::Dark; width]; height])
     }
 
-    pub fn set(&mut self, x: usize, y: usize, pixel: &Pixel) -> Result<()> {
+    pub fn set(&mut self, x: usize, y: usize, pixel: &Pixel) -> Result<(), Error> {
         *self
             .0
             .get_mut(y)
-            .context("get column to set pixel")?
-            .get_mut(x)
-            .context("get pixel to set")? = *pixel;
+            .and_then(|row| row.get_mut(x))
+            .ok_or(Error::PixelOutOfBounds { x, y })? = *pixel;
         Ok(())
     }
 }
@@ -81,3 +101,252 @@ impl Display for Screen {
         write!(f, "{}", buffer)
     }
 }
+
+pub fn parse_program(input: &str) -> Result<Vec<Instruction>, Error> {
+    Ok(diagnostic::parse_all(input, newline, Instruction::parser)?)
+}
+
+pub fn signal_strength_sum(instructions: &[Instruction]) -> i32 {
+    let mut x = 1;
+    let mut cycle = 0;
+    let mut samples = Vec::with_capacity(NUM_SAMPLES);
+    for instruction in instructions {
+        for _ in 0..instruction.cycles() {
+            cycle += 1;
+            if (cycle - 20) % 40 == 0 {
+                samples.push(x * cycle);
+            }
+        }
+        match instruction {
+            Instruction::Noop => (),
+            Instruction::Addx(i) => x += i,
+        }
+    }
+    samples.iter().sum()
+}
+
+pub fn render(instructions: &[Instruction]) -> Result<Screen, Error> {
+    let mut screen = Screen::new(WIDTH, HEIGHT);
+
+    let mut x = 1;
+    let mut cycle = 0;
+    for instruction in instructions {
+        for _ in 0..instruction.cycles() {
+            let x_to_draw = cycle % WIDTH as i32;
+            if (x_to_draw - x).abs() <= 1 {
+                // Cast to usize should never panic because the x and y coordinates we are passing
+                // in should always be positive.
+                screen.set(x_to_draw as usize, cycle as usize / WIDTH, &Pixel::Lit)?;
+            }
+            cycle += 1;
+        }
+        match instruction {
+            Instruction::Noop => (),
+            Instruction::Addx(i) => x += i,
+        }
+    }
+    Ok(screen)
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Part1 = i32;
+    type Part2 = Screen;
+
+    fn part1(input: &str) -> anyhow::Result<i32> {
+        Ok(signal_strength_sum(&parse_program(input)?))
+    }
+
+    fn part2(input: &str) -> anyhow::Result<Screen> {
+        Ok(render(&parse_program(input)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(13140, Day10::part1(EXAMPLE).unwrap());
+        assert_eq!(
+            "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+",
+            Day10::part2(EXAMPLE).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let Some(Error::Syntax(diagnostic)) = parse_program("noop\naddx x\naddx 3").err() else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!((2, 6), (diagnostic.line, diagnostic.col));
+        assert_eq!("a number", diagnostic.expected);
+        let Some(Error::Syntax(diagnostic)) = parse_program("noop\nadx 3\n").err() else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!((2, 1), (diagnostic.line, diagnostic.col));
+        assert_eq!("`noop` or `addx`", diagnostic.expected);
+        assert_eq!(
+            Some(Error::PixelOutOfBounds { x: 40, y: 0 }),
+            Screen::new(WIDTH, HEIGHT).set(40, 0, &Pixel::Lit).err()
+        );
+    }
+}
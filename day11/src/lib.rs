@@ -1,20 +1,34 @@
-use anyhow::{Context, Result};
+use common::diagnostic::{self, Diagnostic, ParseResult};
+use common::{literal, Solver};
 use indicatif::ProgressIterator;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{char, digit1, newline, space0, space1, u64};
-use nom::combinator::map;
-use nom::multi::{separated_list0, separated_list1};
+use nom::combinator::{map, opt};
+use nom::error::context;
+use nom::multi::separated_list0;
 use nom::sequence::{delimited, preceded, separated_pair, terminated, tuple};
-use nom::IResult;
 use num::BigUint;
+use thiserror::Error;
 
 use std::cmp::Reverse;
 use std::collections::VecDeque;
 
-const ROUNDS: u32 = 10000;
+const PART1_ROUNDS: u32 = 20;
+const PART1_WORRY_DIVISOR: u64 = 3;
+const PART2_ROUNDS: u32 = 10000;
+const PART2_WORRY_DIVISOR: u64 = 1;
 const TOP_NUMBER_OF_MONKEYS: usize = 2;
-const WORRY_DIVISOR: u64 = 1;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error(transparent)]
+    Syntax(#[from] Diagnostic),
+    #[error("monkey {monkey} throws to monkey {target}, which does not exist")]
+    NoSuchMonkey { monkey: usize, target: usize },
+    #[error("monkey {monkey} tests divisibility by zero")]
+    DivisibleByZero { monkey: usize },
+}
 
 #[derive(Debug)]
 pub struct Scenario {
@@ -22,26 +36,37 @@ pub struct Scenario {
 }
 
 impl Scenario {
-    pub fn throw_item(&mut self, item: BigUint, monkey: usize) -> Result<()> {
+    pub fn throw_item(&mut self, item: BigUint, from: usize, to: usize) -> Result<(), Error> {
         self.monkeys
-            .get_mut(monkey)
-            .context("get monkey to throw to")?
+            .get_mut(to)
+            .ok_or(Error::NoSuchMonkey {
+                monkey: from,
+                target: to,
+            })?
             .give_item(item);
         Ok(())
     }
 
-    pub fn monkey_business(&mut self) -> BigUint {
-        let divisor_product = self.monkeys.iter().map(|m| m.test.divisible_by).product::<u64>();
-        for _ in (0..ROUNDS).progress() {
+    pub fn monkey_business(&mut self, rounds: u32, worry_divisor: u64) -> BigUint {
+        let divisor_product = self
+            .monkeys
+            .iter()
+            .map(|m| m.test.divisible_by)
+            .product::<u64>();
+        for _ in (0..rounds).progress() {
             for i in 0..self.monkeys.len() {
                 let mut monkey_clone = self.monkeys[i].clone();
                 let inspections = monkey_clone.items.len();
 
                 while let Some(mut item) = monkey_clone.next_item() {
-                    item %= divisor_product;
-                    let (item, target) = monkey_clone.decide(item);
-                    self.throw_item(item, target as usize)
-                        .expect("assuming valid input, the target should always exist");
+                    // Reducing modulo the product of all divisors keeps every test's outcome the
+                    // same, but only while worry levels are never divided.
+                    if worry_divisor == 1 {
+                        item %= divisor_product;
+                    }
+                    let (item, target) = monkey_clone.decide(item, worry_divisor);
+                    self.throw_item(item, i, target)
+                        .expect("targets are validated when the scenario is parsed");
                 }
 
                 self.monkeys[i].items.clear();
@@ -56,14 +81,37 @@ impl Scenario {
             .map(|monkey| monkey.inspections)
             .collect::<Vec<_>>();
         inspections.sort_by_key(|i| Reverse(i.clone()));
-        dbg!(&inspections);
         inspections.iter().take(TOP_NUMBER_OF_MONKEYS).product()
     }
 
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_list1(newline, Monkey::parse), |monkeys| Self {
-            monkeys,
-        })(input)
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let scenario = Self {
+            monkeys: diagnostic::parse_all(input, newline, Monkey::parse)?,
+        };
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        for (monkey, Monkey { test, .. }) in self.monkeys.iter().enumerate() {
+            if test.divisible_by == 0 {
+                return Err(Error::DivisibleByZero { monkey });
+            }
+            for target in [test.pass, test.fail] {
+                if target >= self.monkeys.len() {
+                    return Err(Error::NoSuchMonkey { monkey, target });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&str> for Scenario {
+    type Error = Error;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Self::parse(input)
     }
 }
 
@@ -76,15 +124,21 @@ pub struct Monkey {
 }
 
 impl Monkey {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, _) = tuple((tag("Monkey"), space1, digit1, char(':'), newline))(input)?;
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        let (input, _) = tuple((
+            literal!("Monkey"),
+            space1,
+            context("a number", digit1),
+            char(':'),
+            newline,
+        ))(input)?;
         let (input, items) = delimited(
-            tuple((space0, tag("Starting items:"))),
-            separated_list0(char(','), preceded(space1, u64)),
+            tuple((space0, literal!("Starting items:"))),
+            separated_list0(char(','), preceded(space1, context("a number", u64))),
             newline,
         )(input)?;
         let (input, operation) = terminated(Operation::parse, newline)(input)?;
-        let (input, test) = terminated(Test::parse, newline)(input)?;
+        let (input, test) = terminated(Test::parse, opt(newline))(input)?;
         Ok((
             input,
             Self {
@@ -104,7 +158,7 @@ impl Monkey {
         self.items.push_back(item)
     }
 
-    pub fn decide(&mut self, mut worry_level: BigUint) -> (BigUint, usize) {
+    pub fn decide(&mut self, mut worry_level: BigUint, worry_divisor: u64) -> (BigUint, usize) {
         self.inspections += BigUint::from(1u64);
         let value = match self.operation.operand {
             Operand::Value(value) => value.into(),
@@ -114,7 +168,7 @@ impl Monkey {
             Operator::Add => worry_level += value,
             Operator::Multiply => worry_level *= value,
         }
-        worry_level /= WORRY_DIVISOR;
+        worry_level /= worry_divisor;
         let target_monkey = match &worry_level % self.test.divisible_by == 0u32.into() {
             true => self.test.pass,
             false => self.test.fail,
@@ -130,9 +184,9 @@ pub struct Operation {
 }
 
 impl Operation {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
         preceded(
-            tuple((space1, tag("Operation: new = old "))),
+            tuple((space1, literal!("Operation: new = old"), space1)),
             map(
                 separated_pair(Operator::parse, space0, Operand::parse),
                 |(operator, operand)| Operation { operator, operand },
@@ -148,11 +202,14 @@ pub enum Operator {
 }
 
 impl Operator {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        alt((
-            map(char('*'), |_| Operator::Multiply),
-            map(char('+'), |_| Operator::Add),
-        ))(input)
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        context(
+            "`*` or `+`",
+            alt((
+                map(char('*'), |_| Operator::Multiply),
+                map(char('+'), |_| Operator::Add),
+            )),
+        )(input)
     }
 }
 
@@ -163,11 +220,11 @@ pub enum Operand {
 }
 
 impl Operand {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        alt((
-            map(u64, |value| Operand::Value(value.into())),
-            map(tag("old"), |_| Operand::Old),
-        ))(input)
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        context(
+            "a number or `old`",
+            alt((map(u64, Operand::Value), map(tag("old"), |_| Operand::Old))),
+        )(input)
     }
 }
 
@@ -179,20 +236,25 @@ pub struct Test {
 }
 
 impl Test {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, divisible_by) =
-            delimited(tuple((space1, tag("Test: divisible by "))), u64, newline)(input)?;
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        let (input, divisible_by) = delimited(
+            tuple((space1, literal!("Test: divisible by"), space1)),
+            context("a number", u64),
+            newline,
+        )(input)?;
         let (input, pass) = delimited(
-            tuple((space0, tag("If true: throw to monkey "))),
-            u64,
+            tuple((space0, literal!("If true: throw to monkey"), space1)),
+            context("a monkey number", u64),
             newline,
         )(input)?;
-        let (input, fail) =
-            preceded(tuple((space0, tag("If false: throw to monkey "))), u64)(input)?;
+        let (input, fail) = preceded(
+            tuple((space0, literal!("If false: throw to monkey"), space1)),
+            context("a monkey number", u64),
+        )(input)?;
         Ok((
             input,
             Self {
-                divisible_by: divisible_by.into(),
+                divisible_by,
                 pass: pass as usize,
                 fail: fail as usize,
             },
@@ -200,6 +262,35 @@ impl Test {
     }
 }
 
+pub struct Day11;
+
+impl Day11 {
+    fn monkey_business(input: &str, rounds: u32, worry_divisor: u64) -> Result<BigUint, Error> {
+        Ok(Scenario::parse(input)?.monkey_business(rounds, worry_divisor))
+    }
+}
+
+impl Solver for Day11 {
+    type Part1 = BigUint;
+    type Part2 = BigUint;
+
+    fn part1(input: &str) -> anyhow::Result<BigUint> {
+        Ok(Self::monkey_business(
+            input,
+            PART1_ROUNDS,
+            PART1_WORRY_DIVISOR,
+        )?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<BigUint> {
+        Ok(Self::monkey_business(
+            input,
+            PART2_ROUNDS,
+            PART2_WORRY_DIVISOR,
+        )?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,4 +322,29 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn errors() {
+        let monkey = r"Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+        assert_eq!(
+            Some(Error::NoSuchMonkey {
+                monkey: 0,
+                target: 1
+            }),
+            Scenario::try_from(monkey).err()
+        );
+        let Some(Error::Syntax(diagnostic)) =
+            Scenario::parse(&monkey.replace("* 19", "/ 19")).err()
+        else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!((3, 24), (diagnostic.line, diagnostic.col));
+        assert_eq!("`*` or `+`", diagnostic.expected);
+        assert_eq!("  Operation: new = old / 19", diagnostic.source_line);
+    }
 }
@@ -1,17 +1,12 @@
-use std::io::{self, Read};
-
-use anyhow::{Context, Result};
-use day11::Scenario;
-use nom::{combinator::all_consuming, Finish};
+use anyhow::Result;
+use common::Solver;
+use day11::Day11;
 
 pub fn main() -> Result<()> {
-    let mut buffer = String::new();
-    let mut stdin = io::stdin().lock();
-    stdin.read_to_string(&mut buffer).context("read stdin")?;
+    let buffer = common::read_stdin()?;
 
-    let mut scenario = all_consuming(Scenario::parse)(&buffer).finish().unwrap().1;
-    let monkey_business = scenario.monkey_business();
-    println!("Monkey business: {monkey_business}");
+    println!("Monkey business (part 1): {}", Day11::part1(&buffer)?);
+    println!("Monkey business (part 2): {}", Day11::part2(&buffer)?);
 
     Ok(())
 }
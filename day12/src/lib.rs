@@ -1,11 +1,27 @@
-use anyhow::{Context, Error, Result};
+use common::Solver;
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("the height map is empty")]
+    EmptyInput,
+    #[error("line {line}, column {col}: {ch:?} is not a valid height")]
+    InvalidHeight { ch: char, line: usize, col: usize },
+    #[error("no start position `S` was found")]
+    MissingStart,
+    #[error("no best signal position `E` was found")]
+    MissingEnd,
+    #[error("no path reaches the best signal position")]
+    NoPath,
+}
 
 const INFINITY: u32 = u32::MAX;
 
 #[derive(Clone)]
 pub struct HeightMap {
     grid: Vec<Vec<Rc<RefCell<Node>>>>,
+    start: Position,
     starts: Vec<Position>,
     end: Position,
 }
@@ -14,12 +30,13 @@ impl TryFrom<&str> for HeightMap {
     type Error = Error;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let mut start = None;
         let mut starts = Vec::new();
         let mut end = None;
         let mut grid = Vec::new();
 
-        if input.len() == 0 {
-            return Err(Error::msg("empty input"));
+        if input.is_empty() {
+            return Err(Error::EmptyInput);
         }
 
         for (y, line) in input.lines().enumerate() {
@@ -27,6 +44,7 @@ impl TryFrom<&str> for HeightMap {
             for (x, char) in line.chars().enumerate() {
                 let height = match char {
                     'S' => {
+                        start = Some(Position(x, y));
                         starts.push(Position(x, y));
                         0
                     }
@@ -41,7 +59,13 @@ impl TryFrom<&str> for HeightMap {
                         }
                         height
                     }
-                    _ => return Err(Error::msg("invalid node height character")),
+                    ch => {
+                        return Err(Error::InvalidHeight {
+                            ch,
+                            line: y + 1,
+                            col: x + 1,
+                        })
+                    }
                 };
                 let node = Node {
                     position: Position(x, y),
@@ -95,8 +119,9 @@ impl TryFrom<&str> for HeightMap {
 
         Ok(Self {
             grid,
+            start: start.ok_or(Error::MissingStart)?,
             starts,
-            end: end.context("no end node was identified")?,
+            end: end.ok_or(Error::MissingEnd)?,
         })
     }
 }
@@ -146,7 +171,7 @@ pub struct Dijkstra {
 impl Dijkstra {
     pub fn solve(&mut self) -> Option<u32> {
         self.unvisited_nodes
-            .get_mut(&self.current)
+            .get_mut(&self.start)
             .unwrap()
             .borrow_mut()
             .tentative_distance = 0;
@@ -170,8 +195,7 @@ impl Dijkstra {
 
     fn step(&mut self) -> State {
         self.current = self.lowest_distance_node();
-        self.mark_as_visited(self.current)
-            .expect("nodes we try to move should always exist");
+        self.mark_as_visited(self.current);
         let current_node = self
             .all_nodes
             .get(&self.current)
@@ -212,35 +236,46 @@ impl Dijkstra {
         position.expect("the algorithm should end before there are no more unvisited nodes")
     }
 
-    fn mark_as_visited(&mut self, position: Position) -> Result<()> {
+    fn mark_as_visited(&mut self, position: Position) {
         self.unvisited_nodes
             .remove(&position)
-            .context("removing unvisited node")
-            .map(|_| Ok(()))?
+            .expect("nodes we try to move should always exist");
+    }
+}
+
+impl HeightMap {
+    fn dijkstra_from(&self, start: Position) -> Dijkstra {
+        let nodes: HashMap<_, Rc<RefCell<_>>> = self
+            .grid
+            .iter()
+            .flatten()
+            .map(|node| (node.borrow().position, node.clone()))
+            .collect();
+        Dijkstra {
+            start,
+            end: self.end,
+            current: start,
+            all_nodes: nodes.clone(),
+            unvisited_nodes: nodes,
+        }
+    }
+}
+
+impl From<&HeightMap> for Dijkstra {
+    fn from(height_map: &HeightMap) -> Self {
+        height_map.dijkstra_from(height_map.start)
     }
 }
 
 impl From<&HeightMap> for MultiStartDijkstra {
     fn from(height_map: &HeightMap) -> Self {
-        let mut dijkstras = Vec::new();
-        for start in &height_map.starts {
-            let h = height_map.clone();
-            let unvisited_nodes: HashMap<_, Rc<RefCell<_>>> = h
-                .grid
+        Self(
+            height_map
+                .starts
                 .iter()
-                .flatten()
-                .map(|node| (node.borrow().position, node.clone()))
-                .collect();
-            dijkstras.push(Dijkstra {
-                start: *start,
-                end: h.end,
-                current: *start,
-                all_nodes: unvisited_nodes.clone(),
-                unvisited_nodes: unvisited_nodes.clone(),
-            });
-        }
-
-        Self(dijkstras)
+                .map(|start| height_map.dijkstra_from(*start))
+                .collect(),
+        )
     }
 }
 
@@ -274,3 +309,50 @@ pub enum State {
     Found,
     NoPossiblePath,
 }
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn part1(input: &str) -> anyhow::Result<u32> {
+        let height_map = HeightMap::try_from(input)?;
+        Ok(Dijkstra::from(&height_map).solve().ok_or(Error::NoPath)?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<u32> {
+        let height_map = HeightMap::try_from(input)?;
+        Ok(MultiStartDijkstra::from(&height_map)
+            .solve()
+            .ok_or(Error::NoPath)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
+
+    #[test]
+    fn example() {
+        assert_eq!(31, Day12::part1(EXAMPLE).unwrap());
+        assert_eq!(29, Day12::part2(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Some(Error::EmptyInput), HeightMap::try_from("").err());
+        assert_eq!(
+            Some(Error::InvalidHeight {
+                ch: '?',
+                line: 2,
+                col: 3
+            }),
+            HeightMap::try_from("Sab\nab?E").err()
+        );
+        assert_eq!(Some(Error::MissingStart), HeightMap::try_from("abE").err());
+        assert_eq!(Some(Error::MissingEnd), HeightMap::try_from("Sab").err());
+    }
+}
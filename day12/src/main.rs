@@ -1,19 +1,16 @@
-use std::io::{self, Read};
+use anyhow::Result;
+use common::Solver;
 
-use anyhow::{Result, Context};
-
-use day12::{HeightMap, MultiStartDijkstra};
+use day12::Day12;
 
 pub fn main() -> Result<()> {
-    let mut buffer = String::new();
-    let mut stdin = io::stdin().lock();
-    stdin.read_to_string(&mut buffer).context("read stdin")?;
-
-    let height_map = HeightMap::try_from(buffer.as_str()).context("parsing input")?;
-    let mut multi_start_dijkstra = MultiStartDijkstra::from(&height_map);
+    let buffer = common::read_stdin()?;
 
-    let shortest_distance = multi_start_dijkstra.solve().context("solving using Dijkstra's")?;
-    println!("Shortest distance: {shortest_distance}");
+    println!("Shortest distance from start: {}", Day12::part1(&buffer)?);
+    println!(
+        "Shortest distance from any lowest point: {}",
+        Day12::part2(&buffer)?
+    );
 
     Ok(())
 }
@@ -1,23 +1,41 @@
 use std::cmp::Ordering;
 
+use common::{
+    diagnostic::{self, Diagnostic, ParseResult},
+    Solver,
+};
 use nom::{
     branch::alt,
     character::complete::{char, newline, u32},
-    combinator::map,
+    combinator::{cut, map},
     multi::separated_list0,
-    sequence::{delimited, terminated, tuple},
-    Finish, IResult,
+    sequence::{delimited, pair, separated_pair},
+    Finish,
 };
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error(transparent)]
+    Syntax(#[from] Diagnostic),
+    #[error("no divider packets found")]
+    NoDividerPackets,
+}
 
 pub struct Pair(pub Entry, pub Entry);
 
 impl Pair {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
+    pub fn parse_all(input: &str) -> Result<Vec<Self>, Error> {
+        Ok(diagnostic::parse_all(
+            input,
+            pair(newline, newline),
+            Pair::parse,
+        )?)
+    }
+
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
         map(
-            tuple((
-                terminated(Entry::parse, newline),
-                terminated(Entry::parse, newline),
-            )),
+            separated_pair(Entry::parse, newline, Entry::parse),
             |(a, b)| Self(a, b),
         )(input)
     }
@@ -37,16 +55,16 @@ impl Entry {
         ]
     }
 
-    pub fn parse(input: &str) -> IResult<&str, Self> {
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
         alt((
-            map(u32, |n| Self::Value(n)),
+            map(u32, Self::Value),
             map(
                 delimited(
                     char('['),
                     separated_list0(char(','), Self::parse),
-                    char(']'),
+                    cut(char(']')),
                 ),
-                |vec| Self::List(vec),
+                Self::List,
             ),
         ))(input)
     }
@@ -133,3 +151,96 @@ pub enum Outcome {
     IncorrectOrder,
     Inconclusive,
 }
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn part1(input: &str) -> anyhow::Result<usize> {
+        let pairs = Pair::parse_all(input)?;
+        let mut sum_of_indicies = 0;
+
+        for (index, pair) in pairs.iter().enumerate() {
+            if pair.0.check_ordering(&pair.1) == Outcome::CorrectOrder {
+                sum_of_indicies += index + 1;
+            }
+        }
+
+        Ok(sum_of_indicies)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<usize> {
+        let pairs = Pair::parse_all(input)?;
+
+        let mut entries = Vec::with_capacity(pairs.len() * 2);
+        for Pair(lhs, rhs) in pairs {
+            entries.push(lhs);
+            entries.push(rhs);
+        }
+        entries.append(&mut Entry::divider_packets());
+        entries.sort();
+
+        let mut decoder_key = None;
+        for (index, entry) in entries.iter().enumerate() {
+            if !entry.is_divider_packet() {
+                continue;
+            }
+
+            match decoder_key {
+                Some(current) => decoder_key = Some(current * (index + 1)),
+                None => decoder_key = Some(index + 1),
+            }
+        }
+
+        Ok(decoder_key.ok_or(Error::NoDividerPackets)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(13, Day13::part1(EXAMPLE).unwrap());
+        assert_eq!(140, Day13::part2(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        let Some(Error::Syntax(diagnostic)) = Pair::parse_all("[1,2]\n[3]\n\n[1,x]\n[2]\n").err()
+        else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!((4, 3), (diagnostic.line, diagnostic.col));
+        assert_eq!("`]`", diagnostic.expected);
+    }
+}
@@ -1,18 +1,10 @@
-use std::io::{self, Read};
-
-use anyhow::{Context, Result};
-use day14::Simulation;
-use nom::Finish;
+use anyhow::Result;
+use common::Solver;
+use day14::Day14;
 
 pub fn main() -> Result<()> {
-    let mut buffer = String::new();
-    let mut stdin = io::stdin().lock();
-    stdin.read_to_string(&mut buffer).context("read stdin")?;
-
-    let mut simulation = Simulation::parse(buffer.as_str()).finish().unwrap().1;
-    simulation.set_floor();
-    simulation.run_until_complete();
+    let buffer = common::read_stdin()?;
 
-    println!("Units of sand at rest: {}", simulation.amount_of_sand_at_rest());
+    println!("Units of sand at rest: {}", Day14::part2(&buffer)?);
     Ok(())
 }
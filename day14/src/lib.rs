@@ -1,14 +1,24 @@
 use std::{collections::HashSet, fmt::Display};
 
-use anyhow::{Error, Result};
+use common::{
+    diagnostic::{self, Diagnostic, ParseResult},
+    Solver,
+};
 use nom::{
     bytes::complete::tag,
     character::complete::{char, i32, newline},
-    combinator::map,
+    combinator::{cut, map},
+    error::context,
     multi::separated_list1,
     sequence::separated_pair,
-    IResult,
 };
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error(transparent)]
+    Syntax(#[from] Diagnostic),
+}
 
 const SOURCE: Point = Point(500, 0);
 
@@ -16,8 +26,15 @@ const SOURCE: Point = Point(500, 0);
 pub struct Point(pub i32, pub i32);
 
 impl Point {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_pair(i32, char(','), i32), |(x, y)| Self(x, y))(input)
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        map(
+            separated_pair(
+                context("an x coordinate", i32),
+                char(','),
+                context("a y coordinate", i32),
+            ),
+            |(x, y)| Self(x, y),
+        )(input)
     }
 
     fn down(&self) -> Self {
@@ -34,8 +51,8 @@ impl Point {
 pub struct Rock(pub Vec<Point>);
 
 impl Rock {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_list1(tag(" -> "), Point::parse), |v| Self(v))(input)
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        map(separated_list1(tag(" -> "), cut(Point::parse)), Self)(input)
     }
 }
 
@@ -47,13 +64,13 @@ pub struct Simulation {
 }
 
 impl Simulation {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_list1(newline, Rock::parse), |rocks| Self {
-            rocks,
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            rocks: diagnostic::parse_all(input, newline, Rock::parse)?,
             floor: None,
             sand: HashSet::new(),
             falling: HashSet::new(),
-        })(input)
+        })
     }
 
     pub fn set_floor(&mut self) {
@@ -76,24 +93,19 @@ impl Simulation {
             if self.step() == Outcome::SandSpawnBlocked {
                 return;
             }
-            if self.step() == Outcome::Movement && self.floor.is_none() {
-                if self
-                    .falling
-                    .iter()
-                    .fold(true, |acc, e| acc && self.will_fall_forever(e))
-                {
-                    // All blocks which are falling will continue falling forever
-                    return;
-                }
+            if self.step() == Outcome::Movement
+                && self.floor.is_none()
+                && self.falling.iter().all(|e| self.will_fall_forever(e))
+            {
+                // All blocks which are falling will continue falling forever
+                return;
             }
         }
     }
 
     fn step(&mut self) -> Outcome {
-        if self.falling.len() == 0 {
-            if self.spawn_sand().is_err() {
-                return Outcome::SandSpawnBlocked;
-            }
+        if self.falling.is_empty() && !self.spawn_sand() {
+            return Outcome::SandSpawnBlocked;
         }
         self.apply_gravity()
     }
@@ -102,20 +114,20 @@ impl Simulation {
         let mut outcome = Outcome::NoMovement;
 
         for point in &self.falling.clone() {
-            if !self.down_blocked(&point) {
-                self.falling.remove(&point);
+            if !self.down_blocked(point) {
+                self.falling.remove(point);
                 self.falling.insert(point.down());
                 outcome = Outcome::Movement;
-            } else if !self.down_left_blocked(&point) {
-                self.falling.remove(&point);
+            } else if !self.down_left_blocked(point) {
+                self.falling.remove(point);
                 self.falling.insert(point.down_left());
                 outcome = Outcome::Movement;
-            } else if !self.down_right_blocked(&point) {
-                self.falling.remove(&point);
+            } else if !self.down_right_blocked(point) {
+                self.falling.remove(point);
                 self.falling.insert(point.down_right());
                 outcome = Outcome::Movement;
             } else {
-                self.falling.remove(&point);
+                self.falling.remove(point);
                 self.sand.insert(*point);
             }
         }
@@ -123,11 +135,9 @@ impl Simulation {
         outcome
     }
 
-    fn spawn_sand(&mut self) -> Result<()> {
-        if self.sand.contains(&SOURCE) || !self.falling.insert(SOURCE) {
-            return Err(Error::msg("sand already exists at spawn point"));
-        }
-        Ok(())
+    /// Returns whether sand could be spawned, which it cannot be once the source is blocked.
+    fn spawn_sand(&mut self) -> bool {
+        !self.sand.contains(&SOURCE) && self.falling.insert(SOURCE)
     }
 
     fn down_blocked(&self, current: &Point) -> bool {
@@ -181,7 +191,7 @@ impl Simulation {
                 highest = std::cmp::max(highest, vertex.1);
             }
         }
-        return point.1 >= highest;
+        point.1 >= highest
     }
 
     pub fn amount_of_sand_at_rest(&self) -> usize {
@@ -255,3 +265,57 @@ enum Outcome {
     NoMovement,
     SandSpawnBlocked,
 }
+
+pub struct Day14;
+
+impl Day14 {
+    fn amount_of_sand_at_rest(input: &str, with_floor: bool) -> Result<usize, Error> {
+        let mut simulation = Simulation::parse(input)?;
+        if with_floor {
+            simulation.set_floor();
+        }
+        simulation.run_until_complete();
+        Ok(simulation.amount_of_sand_at_rest())
+    }
+}
+
+impl Solver for Day14 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn part1(input: &str) -> anyhow::Result<usize> {
+        Ok(Self::amount_of_sand_at_rest(input, false)?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<usize> {
+        Ok(Self::amount_of_sand_at_rest(input, true)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+
+    #[test]
+    fn example() {
+        assert_eq!(24, Day14::part1(EXAMPLE).unwrap());
+        assert_eq!(93, Day14::part2(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        let Some(Error::Syntax(diagnostic)) =
+            Simulation::parse("498,4 -> 498,6 -> 496,6\n503,4 -> 502,x").err()
+        else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!(
+            "line 2, column 14: expected a y coordinate\n\
+             503,4 -> 502,x\n\
+             \x20            ^",
+            diagnostic.to_string()
+        );
+    }
+}
@@ -1,26 +1,38 @@
-use std::{fmt::Display, ops::Add};
+use std::{collections::HashSet, fmt::Display, ops::Add};
 
+use common::{
+    diagnostic::{self, Diagnostic, ParseResult},
+    literal, Solver,
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::{i64, newline},
     combinator::map,
-    multi::separated_list1,
+    error::context,
     sequence::{preceded, separated_pair, tuple},
-    IResult,
 };
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error(transparent)]
+    Syntax(#[from] Diagnostic),
+    #[error("no position is left uncovered for the distress beacon")]
+    NoDistressBeacon,
+}
 
 const MAX_COORDINATE: i64 = 4000000;
+const ROW: i64 = 2000000;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Point(pub i64, pub i64);
 
 impl Point {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
         map(
             separated_pair(
-                preceded(tag("x="), i64),
-                tag(", "),
-                preceded(tag("y="), i64),
+                preceded(literal!("x="), context("a number", i64)),
+                literal!(", "),
+                preceded(literal!("y="), context("a number", i64)),
             ),
             |(x, y)| Self(x, y),
         )(input)
@@ -55,11 +67,11 @@ pub struct Sensor {
 }
 
 impl Sensor {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
         map(
             tuple((
-                preceded(tag("Sensor at "), Point::parse),
-                preceded(tag(": closest beacon is at "), Point::parse),
+                preceded(literal!("Sensor at "), Point::parse),
+                preceded(literal!(": closest beacon is at "), Point::parse),
             )),
             |(location, closest_beacon)| Self {
                 location,
@@ -74,10 +86,10 @@ pub struct Area {
 }
 
 impl Area {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_list1(newline, Sensor::parse), |sensors| Self {
-            sensors,
-        })(input)
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            sensors: diagnostic::parse_all(input, newline, Sensor::parse)?,
+        })
     }
 
     pub fn ranges_covered_in_row(&self, y: i64) -> Vec<(i64, i64)> {
@@ -102,6 +114,18 @@ impl Area {
         coverage
     }
 
+    pub fn impossible_positions_in_row(&self, y: i64) -> usize {
+        let mut points_covered_in_row = HashSet::new();
+        for range in &self.ranges_covered_in_row(y) {
+            for point in range.0..=range.1 {
+                if !self.point_contains_item(&Point(point, y)) {
+                    points_covered_in_row.insert(point);
+                }
+            }
+        }
+        points_covered_in_row.len()
+    }
+
     pub fn point_contains_item(&self, point: &Point) -> bool {
         for sensor in &self.sensors {
             if sensor.location == *point || sensor.closest_beacon == *point {
@@ -137,12 +161,14 @@ impl Area {
         is_covered
     }
 
-    pub fn locate_distress_beacon(&self) -> Option<Point> {
-        for y in 0..=MAX_COORDINATE {
+    /// The only position with both coordinates between 0 and `max_coordinate` that no sensor
+    /// covers.
+    pub fn locate_distress_beacon(&self, max_coordinate: i64) -> Option<Point> {
+        for y in 0..=max_coordinate {
             let row_coverage = self.ranges_covered_in_row(y);
             let mut x = 0;
             loop {
-                if x > MAX_COORDINATE {
+                if x > max_coordinate {
                     break;
                 }
                 if let Some((_, high)) = row_coverage
@@ -162,3 +188,77 @@ impl Area {
         None
     }
 }
+
+pub struct Day15;
+
+impl Day15 {
+    fn parse_area(input: &str) -> Result<Area, Error> {
+        Area::parse(input)
+    }
+}
+
+impl Solver for Day15 {
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn part1(input: &str) -> anyhow::Result<usize> {
+        Ok(Self::parse_area(input)?.impossible_positions_in_row(ROW))
+    }
+
+    fn part2(input: &str) -> anyhow::Result<i64> {
+        Ok(Self::parse_area(input)?
+            .locate_distress_beacon(MAX_COORDINATE)
+            .ok_or(Error::NoDistressBeacon)?
+            .tuning_frequency())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn example() {
+        let area = Area::parse(EXAMPLE).unwrap();
+        assert_eq!(26, area.impossible_positions_in_row(10));
+        assert_eq!(
+            Some(56000011),
+            area.locate_distress_beacon(20)
+                .map(|beacon| beacon.tuning_frequency())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let Some(Error::Syntax(diagnostic)) = Area::parse(
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
+             Sensor at x=9, y=16: closest beacon at x=10, y=16",
+        )
+        .err() else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!(
+            "line 2, column 20: expected `: closest beacon is at `\n\
+             Sensor at x=9, y=16: closest beacon at x=10, y=16\n\
+             \x20                  ^",
+            diagnostic.to_string()
+        );
+    }
+}
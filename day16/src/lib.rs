@@ -4,15 +4,26 @@ use std::{
     fmt::Display,
 };
 
+use common::{
+    diagnostic::{self, Diagnostic, ParseResult},
+    literal, Solver, Unsolved,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alpha1, newline},
-    combinator::map,
+    character::complete::{alpha1, newline, space1},
+    combinator::{cut, map},
+    error::context,
     multi::separated_list1,
     sequence::{preceded, tuple},
-    IResult,
 };
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error(transparent)]
+    Syntax(#[from] Diagnostic),
+}
 
 const TOTAL_MINUTES: u64 = 30;
 
@@ -24,17 +35,24 @@ pub struct Valve {
 }
 
 impl Valve {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
         map(
             tuple((
-                preceded(tag("Valve "), alpha1),
-                preceded(tag(" has flow rate="), nom::character::complete::u64),
+                preceded(literal!("Valve "), context("a valve name", alpha1)),
+                preceded(
+                    literal!(" has flow rate="),
+                    context("a flow rate", nom::character::complete::u64),
+                ),
                 preceded(
-                    alt((
-                        tag("; tunnels lead to valves "),
-                        tag("; tunnel leads to valve "),
+                    tuple((
+                        literal!("; "),
+                        alt((
+                            literal!("tunnels lead to valves"),
+                            literal!("tunnel leads to valve"),
+                        )),
+                        space1,
                     )),
-                    separated_list1(tag(", "), alpha1),
+                    separated_list1(tag(", "), cut(context("a valve name", alpha1))),
                 ),
             )),
             |(name, flow_rate, neighbours): (&str, u64, Vec<&str>)| Self {
@@ -51,13 +69,13 @@ pub struct Graph {
 }
 
 impl Graph {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_list1(newline, Valve::parse), |vec| Self {
-            valves: vec
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            valves: diagnostic::parse_all(input, newline, Valve::parse)?
                 .into_iter()
                 .map(|valve| (valve.name.clone(), valve))
                 .collect(),
-        })(input)
+        })
     }
 
     pub fn solve_recurse(&mut self, mut state: State) -> u64 {
@@ -75,16 +93,16 @@ impl Graph {
         }
         let paths = self.shortest_paths(&state.location);
         let mut choices: Vec<(u64, Path)> = paths
-            .into_iter()
-            .map(|(_, path)| (path.expected_benefit(self, &state), path))
+            .into_values()
+            .map(|path| (path.expected_benefit(self, &state), path))
             .collect();
-        choices.sort_by(|a, b| b.0.cmp(&a.0));
+        choices.sort_by_key(|choice| cmp::Reverse(choice.0));
         let top_n_choices = 10;
         choices
             .into_iter()
             .take(top_n_choices)
             .map(|(_, path)| self.solve_recurse(state.clone().apply(&path, self)))
-            .fold(0, |acc, e| cmp::max(acc, e))
+            .fold(0, cmp::max)
     }
 
     pub fn solve(&mut self) -> u64 {
@@ -229,7 +247,7 @@ impl State {
     }
 
     fn apply(self, path: &Path, graph: &Graph) -> State {
-        if path.0.len() == 0 {
+        if path.0.is_empty() {
             return self.do_nothing().1;
         }
         let mut state = self;
@@ -258,6 +276,22 @@ enum MoveOutcome {
     TimeLimitReached,
 }
 
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn part1(input: &str) -> anyhow::Result<u64> {
+        let mut graph = Graph::parse(input)?;
+        Ok(graph.solve())
+    }
+
+    fn part2(_input: &str) -> anyhow::Result<u64> {
+        Err(Unsolved.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,7 +310,23 @@ Valve II has flow rate=0; tunnels lead to valves AA, JJ
 Valve JJ has flow rate=21; tunnel leads to valve II",
         )
         .unwrap()
-        .1
+    }
+
+    #[test]
+    fn errors() {
+        let Some(Error::Syntax(diagnostic)) = Graph::parse(
+            "Valve AA has flow rate=0; tunnels lead to valves BB\n\
+             Valve BB has flow rate=13; tunnel lead to valve AA",
+        )
+        .err() else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!(
+            "line 2, column 28: expected `tunnel leads to valve`\n\
+             Valve BB has flow rate=13; tunnel lead to valve AA\n\
+             \x20                          ^",
+            diagnostic.to_string()
+        );
     }
 
     #[test]
@@ -0,0 +1,297 @@
+use std::sync::OnceLock;
+
+use common::Solver;
+use rules::{Move, Ruleset};
+use thiserror::Error;
+
+pub mod optimizer;
+pub mod rules;
+pub mod tournament;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("{ch:?} is not a valid choice")]
+    NotAChoice { ch: char },
+    #[error("{ch:?} is not a valid outcome")]
+    NotAnOutcome { ch: char },
+    #[error("line {line}, column {col}: {ch:?} is not a valid choice")]
+    InvalidChoice { ch: char, line: usize, col: usize },
+    #[error("line {line}, column {col}: {ch:?} is not a valid outcome")]
+    InvalidOutcome { ch: char, line: usize, col: usize },
+    #[error("line {line}, column {col}: {ch:?} is not X, Y or Z")]
+    InvalidColumn { ch: char, line: usize, col: usize },
+    #[error("line {line}: expected two single characters separated by a space, got {round:?}")]
+    MalformedRound { round: String, line: usize },
+    #[error("line {line}: no move gives the outcome {outcome:?}")]
+    NoMoveForOutcome { outcome: Outcome, line: usize },
+    #[error("a game needs at least one move")]
+    EmptyRuleset,
+    #[error("a game can have at most {max} moves, got {count}", max = rules::MAX_MOVES)]
+    TooManyMoves { count: usize },
+    #[error("{name} is listed more than once")]
+    DuplicateMove { name: String },
+    #[error("move {index} is not rock, paper or scissors")]
+    NotAClassicMove { index: usize },
+    #[error("{name} is not a move in this game")]
+    UnknownMove { name: String },
+    #[error("{name} cannot beat itself")]
+    SelfBeating { name: String },
+    #[error("{first} and {second} cannot both beat each other")]
+    MutualBeats { first: String, second: String },
+    #[error("expected a score for each of the {expected} moves, got {got}")]
+    MoveScoreCount { expected: usize, got: usize },
+    #[error("strategy guide {name} has no rounds")]
+    EmptyGuide { name: String },
+    #[error("a tournament needs at least two players, got {count}")]
+    NotEnoughPlayers { count: usize },
+}
+
+/// The rules the puzzle is played by.
+fn classic() -> &'static Ruleset {
+    static CLASSIC: OnceLock<Ruleset> = OnceLock::new();
+    CLASSIC.get_or_init(Ruleset::classic)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Choice {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Choice {
+    const ALL: [Self; 3] = [Self::Rock, Self::Paper, Self::Scissors];
+
+    pub fn score(&self) -> u32 {
+        classic().move_score((*self).into())
+    }
+}
+
+impl From<Choice> for Move {
+    fn from(choice: Choice) -> Self {
+        Move(choice as usize)
+    }
+}
+
+impl TryFrom<Move> for Choice {
+    type Error = Error;
+
+    fn try_from(Move(index): Move) -> Result<Self, Self::Error> {
+        Self::ALL
+            .get(index)
+            .copied()
+            .ok_or(Error::NotAClassicMove { index })
+    }
+}
+
+impl TryFrom<char> for Choice {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'A' | 'X' => Ok(Self::Rock),
+            'B' | 'Y' => Ok(Self::Paper),
+            'C' | 'Z' => Ok(Self::Scissors),
+            ch => Err(Error::NotAChoice { ch }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+
+impl Outcome {
+    pub fn score(&self) -> u32 {
+        match self {
+            Self::Win => 6,
+            Self::Lose => 0,
+            Self::Draw => 3,
+        }
+    }
+}
+
+impl Outcome {
+    pub fn choice_for_outcome(&self, their_choice: &Choice) -> Choice {
+        classic()
+            .move_for_outcome(*self, (*their_choice).into())
+            .expect("every outcome is possible in rock, paper, scissors")
+            .try_into()
+            .expect("the classic ruleset only has rock, paper and scissors")
+    }
+}
+
+impl TryFrom<char> for Outcome {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'X' => Ok(Self::Lose),
+            'Y' => Ok(Self::Draw),
+            'Z' => Ok(Self::Win),
+            ch => Err(Error::NotAnOutcome { ch }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Round {
+    pub our_choice: Choice,
+    pub their_choice: Choice,
+}
+
+impl Round {
+    pub fn play(&self) -> Outcome {
+        classic().outcome(&(*self).into())
+    }
+
+    pub fn score(&self) -> u32 {
+        classic().score(&(*self).into())
+    }
+}
+
+/// Splits a line of the strategy guide into its two columns, each of which is a single character.
+fn split_round(round: &str, line: usize) -> Result<(char, char), Error> {
+    let mut chars = round.chars();
+    match (chars.next(), chars.next(), chars.next(), chars.next()) {
+        (Some(first), Some(' '), Some(second), None) => Ok((first, second)),
+        _ => Err(Error::MalformedRound {
+            round: round.to_string(),
+            line,
+        }),
+    }
+}
+
+fn parse_choice(ch: char, line: usize, col: usize) -> Result<Choice, Error> {
+    Choice::try_from(ch).map_err(|_| Error::InvalidChoice { ch, line, col })
+}
+
+fn parse_outcome(ch: char, line: usize, col: usize) -> Result<Outcome, Error> {
+    Outcome::try_from(ch).map_err(|_| Error::InvalidOutcome { ch, line, col })
+}
+
+/// Parses each line of the strategy guide as their choice followed by our choice.
+pub fn parse_rounds(input: &str) -> Result<Vec<Round>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, round)| {
+            let line = index + 1;
+            let (their_choice, our_choice) = split_round(round, line)?;
+            Ok(Round {
+                our_choice: parse_choice(our_choice, line, 3)?,
+                their_choice: parse_choice(their_choice, line, 1)?,
+            })
+        })
+        .collect()
+}
+
+/// Parses each line of the strategy guide as their choice followed by the outcome we need, and
+/// picks our choice accordingly.
+pub fn parse_rounds_for_outcomes(input: &str) -> Result<Vec<Round>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, round)| {
+            let line = index + 1;
+            let (their_choice, outcome) = split_round(round, line)?;
+            let their_choice = parse_choice(their_choice, line, 1)?;
+            let outcome = parse_outcome(outcome, line, 3)?;
+            Ok(Round {
+                our_choice: outcome.choice_for_outcome(&their_choice),
+                their_choice,
+            })
+        })
+        .collect()
+}
+
+/// Total score when the second column is our choice.
+pub fn solve_part1(input: &str) -> Result<u32, Error> {
+    Ok(parse_rounds(input)?.iter().map(Round::score).sum())
+}
+
+/// Total score when the second column is the outcome we need.
+pub fn solve_part2(input: &str) -> Result<u32, Error> {
+    Ok(parse_rounds_for_outcomes(input)?
+        .iter()
+        .map(Round::score)
+        .sum())
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn part1(input: &str) -> anyhow::Result<u32> {
+        Ok(solve_part1(input)?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<u32> {
+        Ok(solve_part2(input)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn example() {
+        assert_eq!(15, solve_part1(EXAMPLE).unwrap());
+        assert_eq!(12, solve_part2(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Some(Error::InvalidChoice {
+                ch: 'D',
+                line: 2,
+                col: 1
+            }),
+            parse_rounds("A Y\nD X\n").err()
+        );
+        assert_eq!(
+            Some(Error::InvalidOutcome {
+                ch: 'A',
+                line: 1,
+                col: 3
+            }),
+            parse_rounds_for_outcomes("A A\n").err()
+        );
+        assert_eq!(
+            Some(Error::MalformedRound {
+                round: "AY".into(),
+                line: 1
+            }),
+            parse_rounds("AY\n").err()
+        );
+        assert_eq!(Err(Error::NotAChoice { ch: 'D' }), Choice::try_from('D'));
+        assert_eq!(
+            Err(Error::NotAClassicMove { index: 3 }),
+            Choice::try_from(Move(3))
+        );
+        assert_eq!(Err(Error::NotAnOutcome { ch: 'A' }), Outcome::try_from('A'));
+    }
+
+    #[test]
+    fn choice_for_outcome() {
+        use Choice::*;
+        for their_choice in [Rock, Paper, Scissors] {
+            for outcome in [Outcome::Win, Outcome::Lose, Outcome::Draw] {
+                let round = Round {
+                    our_choice: outcome.choice_for_outcome(&their_choice),
+                    their_choice,
+                };
+                assert_eq!(outcome, round.play());
+            }
+        }
+    }
+}
@@ -1,157 +1,68 @@
-use anyhow::{Context, Result};
-use std::io::{self, Read};
-
-#[derive(Clone, Copy, PartialEq)]
-enum Choice {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-impl Choice {
-    fn score(&self) -> u32 {
-        match self {
-            Choice::Rock => 1,
-            Choice::Paper => 2,
-            Choice::Scissors => 3,
-        }
-    }
-}
-
-impl TryFrom<char> for Choice {
-    type Error = anyhow::Error;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'A' | 'X' => Ok(Self::Rock),
-            'B' | 'Y' => Ok(Self::Paper),
-            'C' | 'Z' => Ok(Self::Scissors),
-            _ => Err(anyhow::Error::msg("invalid choice character")),
-        }
-    }
-}
-
-enum Outcome {
-    Win,
-    Lose,
-    Draw,
+use std::{env, fs};
+
+use anyhow::{Context, Error, Result};
+use day2::{
+    rules::Ruleset,
+    tournament::{Player, Tournament},
+};
+
+const USAGE: &str = "\
+Usage:
+    day2
+    day2 --optimize
+    day2 --rules classic|rpsls
+    day2 --tournament round-robin|elimination GUIDE...
+
+Without options, prints both puzzle answers. --rules prints them for rock, paper, scissors or
+for rock, paper, scissors, lizard, Spock, where A-E and V-Z name the five moves. --optimize
+scores the guide under every reading of X, Y and Z and shows round by round how the highest and
+lowest totals come about. --tournament plays the moves in the given strategy guide files
+against each other and prints the standings.";
+
+fn tournament(format: &str, paths: &[&str]) -> Result<Tournament> {
+    let players = paths
+        .iter()
+        .map(|path| {
+            let guide = fs::read_to_string(path).with_context(|| format!("reading {path}"))?;
+            Player::parse(path, &guide).with_context(|| format!("parsing {path}"))
+        })
+        .collect::<Result<Vec<Player>>>()?;
+    Ok(match format {
+        "round-robin" => Tournament::round_robin(players)?,
+        "elimination" => Tournament::single_elimination(players)?,
+        format => return Err(Error::msg(format!("{format} is not a tournament format"))),
+    })
 }
 
-impl Outcome {
-    fn score(&self) -> u32 {
-        match self {
-            Self::Win => 6,
-            Self::Lose => 0,
-            Self::Draw => 3,
-        }
-    }
-}
-
-impl Outcome {
-    fn choice_for_outcome(&self, their_choice: &Choice) -> Choice {
-        use Choice::*;
-        match (self, their_choice) {
-            (Self::Win, Rock) => Paper,
-            (Self::Win, Paper) => Scissors,
-            (Self::Win, Scissors) => Rock,
-            (Self::Lose, Rock) => Scissors,
-            (Self::Lose, Paper) => Rock,
-            (Self::Lose, Scissors) => Paper,
-            (Self::Draw, _) => *their_choice,
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        [] => {
+            let buffer = common::read_stdin()?;
+            println!("Total score (part 1): {}", day2::solve_part1(&buffer)?);
+            println!("Total score (part 2): {}", day2::solve_part2(&buffer)?);
         }
-    }
-}
-
-impl TryFrom<char> for Outcome {
-    type Error = anyhow::Error;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'X' => Ok(Self::Lose),
-            'Y' => Ok(Self::Draw),
-            'Z' => Ok(Self::Win),
-            _ => Err(anyhow::Error::msg("invalid outcome character")),
+        ["--optimize"] => {
+            let buffer = common::read_stdin()?;
+            println!("{}", day2::optimizer::optimize(&buffer)?);
         }
-    }
-}
-
-struct Round {
-    pub our_choice: Choice,
-    pub their_choice: Choice,
-}
-
-impl Round {
-    fn play(&self) -> Outcome {
-        use Choice::*;
-
-        match (&self.our_choice, &self.their_choice) {
-            (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper) => Outcome::Win,
-            (Rock, Paper) | (Paper, Scissors) | (Scissors, Rock) => Outcome::Lose,
-            _ => Outcome::Draw,
+        ["--rules", rules] => {
+            let rules = match *rules {
+                "classic" => Ruleset::classic(),
+                "rpsls" => Ruleset::rpsls(),
+                rules => return Err(Error::msg(format!("{rules} is not a known game"))),
+            };
+            let buffer = common::read_stdin()?;
+            let part1 = rules.total_score(&rules.parse_rounds(&buffer)?);
+            let part2 = rules.total_score(&rules.parse_rounds_for_outcomes(&buffer)?);
+            println!("Total score (part 1): {part1}");
+            println!("Total score (part 2): {part2}");
         }
+        ["--tournament", format, paths @ ..] => println!("{}", tournament(format, paths)?),
+        _ => return Err(Error::msg(USAGE)),
     }
-    fn score(&self) -> u32 {
-        self.our_choice.score() + self.play().score()
-    }
-}
-
-fn main() -> Result<()> {
-    let mut buffer = String::new();
-    io::stdin()
-        .lock()
-        .read_to_string(&mut buffer)
-        .context("reading stdin")?;
-
-    let mut total_score = 0;
-
-    for line in buffer.lines() {
-        let (their_choice, our_choice) = line
-            .split_once(' ')
-            .context("splitting string to get choices")?;
-        let their_choice = Choice::try_from(
-            their_choice
-                .chars()
-                .next()
-                .context("their choice string to char")?,
-        )
-        .context("parsing their choice from char")?;
-        let our_choice = Choice::try_from(
-            our_choice
-                .chars()
-                .next()
-                .context("our choice string to char")?,
-        )
-        .context("parsing our choice from char")?;
-        let round = Round {
-            our_choice,
-            their_choice,
-        };
-        total_score += round.score();
-    }
-    println!("Total score (part 1): {total_score}");
-
-    total_score = 0;
-    for line in buffer.lines() {
-        let (their_choice, outcome) = line
-            .split_once(' ')
-            .context("splitting string to get their choice and outcome")?;
-        let their_choice = Choice::try_from(
-            their_choice
-                .chars()
-                .next()
-                .context("their choice string to char")?,
-        )
-        .context("parsing their choice from char")?;
-        let outcome = Outcome::try_from(outcome.chars().next().context("outcome string to char")?)
-            .context("parsing outcome from char")?;
-        let our_choice = outcome.choice_for_outcome(&their_choice);
-        let round = Round {
-            our_choice,
-            their_choice,
-        };
-        total_score += round.score();
-    }
-    println!("Total score (part 2): {total_score}");
 
     Ok(())
 }
@@ -0,0 +1,257 @@
+use std::fmt::Display;
+
+use crate::{parse_choice, split_round, Choice, Error, Outcome, Round};
+
+/// How the X, Y and Z in the second column of a strategy guide are read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpretation {
+    /// X, Y and Z are our choice, mapped in that order to these choices.
+    Choices([Choice; 3]),
+    /// X, Y and Z are the outcome we need: lose, draw and win.
+    Outcomes,
+}
+
+impl Interpretation {
+    /// Every permutation of the X/Y/Z to choice mapping, followed by the outcome reading.
+    pub fn all() -> [Self; 7] {
+        use Choice::*;
+        [
+            Self::Choices([Rock, Paper, Scissors]),
+            Self::Choices([Rock, Scissors, Paper]),
+            Self::Choices([Paper, Rock, Scissors]),
+            Self::Choices([Paper, Scissors, Rock]),
+            Self::Choices([Scissors, Rock, Paper]),
+            Self::Choices([Scissors, Paper, Rock]),
+            Self::Outcomes,
+        ]
+    }
+
+    fn round(&self, their_choice: Choice, column: usize) -> Round {
+        let our_choice = match self {
+            Self::Choices(choices) => choices[column],
+            Self::Outcomes => [Outcome::Lose, Outcome::Draw, Outcome::Win][column]
+                .choice_for_outcome(&their_choice),
+        };
+        Round {
+            our_choice,
+            their_choice,
+        }
+    }
+}
+
+impl Display for Interpretation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Choices([x, y, z]) => write!(f, "X={x:?} Y={y:?} Z={z:?}"),
+            Self::Outcomes => write!(f, "X=Lose Y=Draw Z=Win"),
+        }
+    }
+}
+
+/// A line of the strategy guide, with the second column left uninterpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Entry {
+    line: usize,
+    their_choice: Choice,
+    /// 0, 1 or 2 for X, Y or Z.
+    column: usize,
+}
+
+fn parse_guide(input: &str) -> Result<Vec<Entry>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, round)| {
+            let line = index + 1;
+            let (their_choice, column) = split_round(round, line)?;
+            let column = match column {
+                'X' => 0,
+                'Y' => 1,
+                'Z' => 2,
+                ch => return Err(Error::InvalidColumn { ch, line, col: 3 }),
+            };
+            Ok(Entry {
+                line,
+                their_choice: parse_choice(their_choice, line, 1)?,
+                column,
+            })
+        })
+        .collect()
+}
+
+/// How a single line of the guide played out under one interpretation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoundBreakdown {
+    pub line: usize,
+    /// The letter in the second column.
+    pub column: char,
+    pub round: Round,
+    pub outcome: Outcome,
+    pub score: u32,
+}
+
+/// The guide scored under one interpretation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Evaluation {
+    pub interpretation: Interpretation,
+    pub total: u32,
+    pub rounds: Vec<RoundBreakdown>,
+}
+
+impl Evaluation {
+    fn new(interpretation: Interpretation, guide: &[Entry]) -> Self {
+        let rounds: Vec<RoundBreakdown> = guide
+            .iter()
+            .map(|entry| {
+                let round = interpretation.round(entry.their_choice, entry.column);
+                RoundBreakdown {
+                    line: entry.line,
+                    column: ['X', 'Y', 'Z'][entry.column],
+                    round,
+                    outcome: round.play(),
+                    score: round.score(),
+                }
+            })
+            .collect();
+        Self {
+            interpretation,
+            total: rounds.iter().map(|round| round.score).sum(),
+            rounds,
+        }
+    }
+}
+
+impl Display for Evaluation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.interpretation, self.total)?;
+        for round in &self.rounds {
+            write!(
+                f,
+                "\n  line {}: {:?} vs {}={:?}, {:?}, {}",
+                round.line,
+                round.round.their_choice,
+                round.column,
+                round.round.our_choice,
+                round.outcome,
+                round.score
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The guide scored under every interpretation, in the order of [`Interpretation::all`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Optimization {
+    pub evaluations: Vec<Evaluation>,
+}
+
+impl Optimization {
+    /// Interpretations giving the highest total, all of them if several are tied.
+    pub fn best(&self) -> Vec<&Evaluation> {
+        self.with_total(self.evaluations.iter().map(|e| e.total).max())
+    }
+
+    /// Interpretations giving the lowest total, all of them if several are tied.
+    pub fn worst(&self) -> Vec<&Evaluation> {
+        self.with_total(self.evaluations.iter().map(|e| e.total).min())
+    }
+
+    fn with_total(&self, total: Option<u32>) -> Vec<&Evaluation> {
+        self.evaluations
+            .iter()
+            .filter(|evaluation| Some(evaluation.total) == total)
+            .collect()
+    }
+}
+
+impl Display for Optimization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for evaluation in &self.evaluations {
+            writeln!(f, "{}: {}", evaluation.interpretation, evaluation.total)?;
+        }
+        writeln!(f, "Highest:")?;
+        for evaluation in self.best() {
+            writeln!(f, "{evaluation}")?;
+        }
+        writeln!(f, "Lowest:")?;
+        let worst: Vec<String> = self.worst().iter().map(ToString::to_string).collect();
+        write!(f, "{}", worst.join("\n"))
+    }
+}
+
+/// Scores the strategy guide under every interpretation of its second column.
+pub fn optimize(input: &str) -> Result<Optimization, Error> {
+    let guide = parse_guide(input)?;
+    Ok(Optimization {
+        evaluations: Interpretation::all()
+            .into_iter()
+            .map(|interpretation| Evaluation::new(interpretation, &guide))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn example() {
+        let optimization = optimize(EXAMPLE).unwrap();
+        let totals: Vec<u32> = optimization.evaluations.iter().map(|e| e.total).collect();
+        assert_eq!(vec![15, 6, 15, 15, 15, 24, 12], totals);
+
+        let best = optimization.best();
+        assert_eq!(1, best.len());
+        assert_eq!(
+            Interpretation::Choices([Choice::Scissors, Choice::Paper, Choice::Rock]),
+            best[0].interpretation
+        );
+        assert_eq!(
+            vec![Interpretation::Choices([
+                Choice::Rock,
+                Choice::Scissors,
+                Choice::Paper
+            ])],
+            optimization
+                .worst()
+                .iter()
+                .map(|e| e.interpretation)
+                .collect::<Vec<_>>()
+        );
+
+        let scores: Vec<u32> = optimization.evaluations[0]
+            .rounds
+            .iter()
+            .map(|round| round.score)
+            .collect();
+        assert_eq!(vec![8, 1, 6], scores);
+        assert_eq!(
+            RoundBreakdown {
+                line: 2,
+                column: 'X',
+                round: Round {
+                    our_choice: Choice::Rock,
+                    their_choice: Choice::Paper
+                },
+                outcome: Outcome::Lose,
+                score: 1
+            },
+            optimization.evaluations[6].rounds[1]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Some(Error::InvalidColumn {
+                ch: 'A',
+                line: 2,
+                col: 3
+            }),
+            optimize("A Y\nB A\n").err()
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use crate::{parse_outcome, split_round, Error, Outcome, Round};
+
+/// Strategy guides name moves by letter, counting up from `A` for their moves and down from `Z`
+/// for ours, so a game can't have more moves than this.
+pub const MAX_MOVES: usize = 13;
+
+/// A move in some [`Ruleset`], identified by its position in the ruleset's list of moves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Move(pub usize);
+
+/// Points for the outcome of a round, from our point of view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutcomeScores {
+    pub win: u32,
+    pub draw: u32,
+    pub lose: u32,
+}
+
+impl OutcomeScores {
+    pub fn score(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Win => self.win,
+            Outcome::Draw => self.draw,
+            Outcome::Lose => self.lose,
+        }
+    }
+}
+
+impl Default for OutcomeScores {
+    fn default() -> Self {
+        Self {
+            win: Outcome::Win.score(),
+            draw: Outcome::Draw.score(),
+            lose: Outcome::Lose.score(),
+        }
+    }
+}
+
+/// One round of any game: our move against theirs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Play {
+    pub our_move: Move,
+    pub their_move: Move,
+}
+
+impl From<Round> for Play {
+    fn from(round: Round) -> Self {
+        Self {
+            our_move: round.our_choice.into(),
+            their_move: round.their_choice.into(),
+        }
+    }
+}
+
+/// A game defined by its moves and which of them beat which. Moves that don't beat each other
+/// draw.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ruleset {
+    names: Vec<String>,
+    /// `beats[a][b]` is true if move `a` beats move `b`.
+    beats: Vec<Vec<bool>>,
+    move_scores: Vec<u32>,
+    outcome_scores: OutcomeScores,
+}
+
+impl Ruleset {
+    /// Builds a game from its moves and `(winner, loser)` pairs. The moves score 1, 2, 3 and so
+    /// on in the order given.
+    pub fn new(names: &[&str], beats: &[(&str, &str)]) -> Result<Self, Error> {
+        if names.is_empty() {
+            return Err(Error::EmptyRuleset);
+        }
+        if names.len() > MAX_MOVES {
+            return Err(Error::TooManyMoves { count: names.len() });
+        }
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].contains(name) {
+                return Err(Error::DuplicateMove {
+                    name: name.to_string(),
+                });
+            }
+        }
+
+        let mut ruleset = Self {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            beats: vec![vec![false; names.len()]; names.len()],
+            move_scores: (1..=names.len() as u32).collect(),
+            outcome_scores: OutcomeScores::default(),
+        };
+        for &(winner, loser) in beats {
+            let (Move(winner), Move(loser)) = (ruleset.find(winner)?, ruleset.find(loser)?);
+            if winner == loser {
+                return Err(Error::SelfBeating {
+                    name: names[winner].to_string(),
+                });
+            }
+            if ruleset.beats[loser][winner] {
+                return Err(Error::MutualBeats {
+                    first: names[winner].to_string(),
+                    second: names[loser].to_string(),
+                });
+            }
+            ruleset.beats[winner][loser] = true;
+        }
+        Ok(ruleset)
+    }
+
+    /// Builds a tournament-style cyclic game in which each move beats the half of the moves
+    /// listed just before it, wrapping around. With an even number of moves, each move draws
+    /// against the one directly opposite it.
+    pub fn cyclic(names: &[&str]) -> Result<Self, Error> {
+        let n = names.len();
+        let beats: Vec<(&str, &str)> = (0..n)
+            .flat_map(|winner| {
+                (1..=(n - 1) / 2).map(move |k| (names[winner], names[(winner + n - k) % n]))
+            })
+            .collect();
+        Self::new(names, &beats)
+    }
+
+    /// Rock, paper, scissors.
+    pub fn classic() -> Self {
+        Self::cyclic(&["Rock", "Paper", "Scissors"]).expect("classic rules are valid")
+    }
+
+    /// Rock, paper, scissors, lizard, Spock.
+    pub fn rpsls() -> Self {
+        Self::new(
+            &["Rock", "Paper", "Scissors", "Lizard", "Spock"],
+            &[
+                ("Scissors", "Paper"),
+                ("Paper", "Rock"),
+                ("Rock", "Lizard"),
+                ("Lizard", "Spock"),
+                ("Spock", "Scissors"),
+                ("Scissors", "Lizard"),
+                ("Lizard", "Paper"),
+                ("Paper", "Spock"),
+                ("Spock", "Rock"),
+                ("Rock", "Scissors"),
+            ],
+        )
+        .expect("rock, paper, scissors, lizard, Spock rules are valid")
+    }
+
+    /// Replaces the points for each move, given in the order of the moves.
+    pub fn with_move_scores(mut self, scores: &[u32]) -> Result<Self, Error> {
+        if scores.len() != self.names.len() {
+            return Err(Error::MoveScoreCount {
+                expected: self.names.len(),
+                got: scores.len(),
+            });
+        }
+        self.move_scores = scores.to_vec();
+        Ok(self)
+    }
+
+    pub fn with_outcome_scores(mut self, scores: OutcomeScores) -> Self {
+        self.outcome_scores = scores;
+        self
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        (0..self.names.len()).map(Move)
+    }
+
+    pub fn name(&self, Move(index): Move) -> &str {
+        &self.names[index]
+    }
+
+    pub fn find(&self, name: &str) -> Result<Move, Error> {
+        self.names
+            .iter()
+            .position(|candidate| candidate == name)
+            .map(Move)
+            .ok_or_else(|| Error::UnknownMove {
+                name: name.to_string(),
+            })
+    }
+
+    pub fn beats(&self, Move(winner): Move, Move(loser): Move) -> bool {
+        self.beats[winner][loser]
+    }
+
+    pub fn outcome(&self, play: &Play) -> Outcome {
+        if self.beats(play.our_move, play.their_move) {
+            Outcome::Win
+        } else if self.beats(play.their_move, play.our_move) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn move_score(&self, Move(index): Move) -> u32 {
+        self.move_scores[index]
+    }
+
+    pub fn score(&self, play: &Play) -> u32 {
+        self.move_score(play.our_move) + self.outcome_scores.score(self.outcome(play))
+    }
+
+    /// The first move that gives `outcome` against `their_move`, if there is one.
+    pub fn move_for_outcome(&self, outcome: Outcome, their_move: Move) -> Option<Move> {
+        self.moves().find(|&our_move| {
+            outcome
+                == self.outcome(&Play {
+                    our_move,
+                    their_move,
+                })
+        })
+    }
+
+    fn parse_move(&self, ch: char, first: u8, line: usize, col: usize) -> Result<Move, Error> {
+        (ch as u32)
+            .checked_sub(first as u32)
+            .map(|index| index as usize)
+            .filter(|&index| index < self.names.len())
+            .map(Move)
+            .ok_or(Error::InvalidChoice { ch, line, col })
+    }
+
+    fn their_letter(&self) -> u8 {
+        b'A'
+    }
+
+    fn our_letter(&self) -> u8 {
+        b'Z' + 1 - self.names.len() as u8
+    }
+
+    /// Parses each line of the strategy guide as their move followed by ours.
+    pub fn parse_rounds(&self, input: &str) -> Result<Vec<Play>, Error> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, round)| {
+                let line = index + 1;
+                let (their_move, our_move) = split_round(round, line)?;
+                Ok(Play {
+                    our_move: self.parse_move(our_move, self.our_letter(), line, 3)?,
+                    their_move: self.parse_move(their_move, self.their_letter(), line, 1)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Parses each line of the strategy guide as their move followed by the outcome we need, and
+    /// picks our move accordingly.
+    pub fn parse_rounds_for_outcomes(&self, input: &str) -> Result<Vec<Play>, Error> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, round)| {
+                let line = index + 1;
+                let (their_move, outcome) = split_round(round, line)?;
+                let their_move = self.parse_move(their_move, self.their_letter(), line, 1)?;
+                let outcome = parse_outcome(outcome, line, 3)?;
+                let our_move = self
+                    .move_for_outcome(outcome, their_move)
+                    .ok_or(Error::NoMoveForOutcome { outcome, line })?;
+                Ok(Play {
+                    our_move,
+                    their_move,
+                })
+            })
+            .collect()
+    }
+
+    pub fn total_score(&self, plays: &[Play]) -> u32 {
+        plays.iter().map(|play| self.score(play)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Choice;
+
+    #[test]
+    fn classic() {
+        let rules = Ruleset::classic();
+        let example = "A Y\nB X\nC Z\n";
+        assert_eq!(15, rules.total_score(&rules.parse_rounds(example).unwrap()));
+        assert_eq!(
+            12,
+            rules.total_score(&rules.parse_rounds_for_outcomes(example).unwrap())
+        );
+        assert!(rules.beats(Choice::Rock.into(), Choice::Scissors.into()));
+        assert!(rules.beats(Choice::Paper.into(), Choice::Rock.into()));
+        assert!(rules.beats(Choice::Scissors.into(), Choice::Paper.into()));
+    }
+
+    #[test]
+    fn rpsls() {
+        let rules = Ruleset::rpsls();
+        for mv in rules.moves() {
+            assert_eq!(
+                2,
+                rules
+                    .moves()
+                    .filter(|&other| rules.beats(mv, other))
+                    .count()
+            );
+        }
+        // Rock draws with rock, their scissors cut our paper and our rock crushes their lizard.
+        let guide = "A V\nC W\nD V\n";
+        assert_eq!(
+            4 + 2 + 7,
+            rules.total_score(&rules.parse_rounds(guide).unwrap())
+        );
+        let plays = rules.parse_rounds_for_outcomes("D X\nB Z\n").unwrap();
+        assert_eq!(
+            vec!["Paper", "Scissors"],
+            plays
+                .iter()
+                .map(|play| rules.name(play.our_move))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn scores() {
+        let rules = Ruleset::cyclic(&["A", "B", "C", "D"])
+            .unwrap()
+            .with_move_scores(&[0, 0, 0, 10])
+            .unwrap()
+            .with_outcome_scores(OutcomeScores {
+                win: 2,
+                draw: 1,
+                lose: 0,
+            });
+        let play = |ours, theirs| Play {
+            our_move: rules.find(ours).unwrap(),
+            their_move: rules.find(theirs).unwrap(),
+        };
+        assert_eq!(Outcome::Draw, rules.outcome(&play("A", "C")));
+        assert_eq!(Outcome::Win, rules.outcome(&play("A", "D")));
+        assert_eq!(10, rules.score(&play("D", "A")));
+        assert_eq!(12, rules.score(&play("D", "C")));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Some(Error::EmptyRuleset), Ruleset::cyclic(&[]).err());
+        assert_eq!(
+            Some(Error::DuplicateMove { name: "A".into() }),
+            Ruleset::cyclic(&["A", "B", "A"]).err()
+        );
+        assert_eq!(
+            Some(Error::MutualBeats {
+                first: "B".into(),
+                second: "A".into()
+            }),
+            Ruleset::new(&["A", "B"], &[("A", "B"), ("B", "A")]).err()
+        );
+        assert_eq!(
+            Some(Error::UnknownMove { name: "C".into() }),
+            Ruleset::new(&["A", "B"], &[("A", "C")]).err()
+        );
+        assert_eq!(
+            Some(Error::MoveScoreCount {
+                expected: 3,
+                got: 2
+            }),
+            Ruleset::classic().with_move_scores(&[1, 2]).err()
+        );
+        assert_eq!(
+            Some(Error::NoMoveForOutcome {
+                outcome: Outcome::Win,
+                line: 1
+            }),
+            Ruleset::new(&["A", "B"], &[])
+                .unwrap()
+                .parse_rounds_for_outcomes("A Z\n")
+                .err()
+        );
+        assert_eq!(
+            Some(Error::InvalidChoice {
+                ch: 'X',
+                line: 1,
+                col: 3
+            }),
+            Ruleset::new(&["A", "B"], &[])
+                .unwrap()
+                .parse_rounds("A X\n")
+                .err()
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    fmt::Display,
+};
+
+use crate::{parse_rounds, Choice, Error, Outcome, Round};
+
+/// A strategy guide entered into a tournament. Only our column of the guide is used: it's the
+/// sequence of moves the player throws in every match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub moves: Vec<Choice>,
+}
+
+impl Player {
+    pub fn parse(name: &str, guide: &str) -> Result<Self, Error> {
+        let moves: Vec<Choice> = parse_rounds(guide)?
+            .iter()
+            .map(|round| round.our_choice)
+            .collect();
+        if moves.is_empty() {
+            return Err(Error::EmptyGuide {
+                name: name.to_string(),
+            });
+        }
+        Ok(Self {
+            name: name.to_string(),
+            moves,
+        })
+    }
+}
+
+/// Two players throwing their moves against each other until the shorter guide runs out. Each
+/// array holds the first player's figure, then the second's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// Indices of the players, the higher seed (lower index) first.
+    pub players: [usize; 2],
+    /// Sum of [`Round::score`] over the match.
+    pub scores: [u32; 2],
+    pub rounds_won: [usize; 2],
+    /// Highest [`Round::score`] in any single round.
+    pub best_round: [u32; 2],
+    /// `None` for a drawn round-robin match.
+    pub winner: Option<usize>,
+}
+
+impl Match {
+    fn play(players: &[Player], first: usize, second: usize, break_ties: bool) -> Self {
+        let mut played = Self {
+            players: [first, second],
+            scores: [0; 2],
+            rounds_won: [0; 2],
+            best_round: [0; 2],
+            winner: None,
+        };
+        for (&a, &b) in players[first].moves.iter().zip(&players[second].moves) {
+            let rounds = [
+                Round {
+                    our_choice: a,
+                    their_choice: b,
+                },
+                Round {
+                    our_choice: b,
+                    their_choice: a,
+                },
+            ];
+            for (side, round) in rounds.iter().enumerate() {
+                let score = round.score();
+                played.scores[side] += score;
+                played.best_round[side] = played.best_round[side].max(score);
+                if round.play() == Outcome::Win {
+                    played.rounds_won[side] += 1;
+                }
+            }
+        }
+
+        let ordering = if break_ties {
+            // Ties on everything else go to the higher seed.
+            played
+                .tally(0)
+                .cmp(&played.tally(1))
+                .then(Ordering::Greater)
+        } else {
+            played.scores[0].cmp(&played.scores[1])
+        };
+        played.winner = match ordering {
+            Ordering::Greater => Some(first),
+            Ordering::Less => Some(second),
+            Ordering::Equal => None,
+        };
+        played
+    }
+
+    /// What a side's result is compared on: total score, then rounds won, then best round.
+    fn tally(&self, side: usize) -> (u32, usize, u32) {
+        (
+            self.scores[side],
+            self.rounds_won[side],
+            self.best_round[side],
+        )
+    }
+}
+
+/// A player's results over the whole tournament.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub player: usize,
+    pub played: usize,
+    pub won: usize,
+    pub drawn: usize,
+    pub lost: usize,
+    /// Cumulative [`Round::score`] over every match played.
+    pub score: u32,
+    pub best_round: u32,
+    /// Number of elimination rounds survived, including byes. Always 0 in a round robin.
+    pub rounds_survived: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tournament {
+    pub players: Vec<Player>,
+    pub matches: Vec<Match>,
+    /// Ordered by rounds survived, cumulative score, matches won and best round, with any
+    /// remaining ties going to the player entered first.
+    pub standings: Vec<Standing>,
+}
+
+impl Tournament {
+    /// Every player meets every other player once.
+    pub fn round_robin(players: Vec<Player>) -> Result<Self, Error> {
+        Self::check(&players)?;
+        let n = players.len();
+        let matches = (0..n)
+            .flat_map(|first| (first + 1..n).map(move |second| (first, second)))
+            .map(|(first, second)| Match::play(&players, first, second, false))
+            .collect();
+        Ok(Self::new(players, matches, vec![0; n]))
+    }
+
+    /// Players are paired off in the order given and winners go through to the next round. With
+    /// an odd number of players left, the last one gets a bye. Drawn matches go to the player
+    /// with more rounds won, then the better best round, then the higher seed.
+    pub fn single_elimination(players: Vec<Player>) -> Result<Self, Error> {
+        Self::check(&players)?;
+        let mut rounds_survived = vec![0; players.len()];
+        let mut matches = Vec::new();
+        let mut remaining: Vec<usize> = (0..players.len()).collect();
+        let mut round = 0;
+        while remaining.len() > 1 {
+            round += 1;
+            remaining = remaining
+                .chunks(2)
+                .map(|pair| match *pair {
+                    [first, second] => {
+                        let played = Match::play(&players, first, second, true);
+                        matches.push(played);
+                        played
+                            .winner
+                            .expect("elimination matches always have a winner")
+                    }
+                    [bye] => bye,
+                    _ => unreachable!(),
+                })
+                .collect();
+            for &player in &remaining {
+                rounds_survived[player] = round;
+            }
+        }
+        Ok(Self::new(players, matches, rounds_survived))
+    }
+
+    fn check(players: &[Player]) -> Result<(), Error> {
+        if players.len() < 2 {
+            return Err(Error::NotEnoughPlayers {
+                count: players.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn new(players: Vec<Player>, matches: Vec<Match>, rounds_survived: Vec<usize>) -> Self {
+        let mut standings: Vec<Standing> = rounds_survived
+            .into_iter()
+            .enumerate()
+            .map(|(player, rounds_survived)| Standing {
+                player,
+                rounds_survived,
+                ..Default::default()
+            })
+            .collect();
+        for played in &matches {
+            for (side, &player) in played.players.iter().enumerate() {
+                let standing = &mut standings[player];
+                standing.played += 1;
+                standing.score += played.scores[side];
+                standing.best_round = standing.best_round.max(played.best_round[side]);
+                match played.winner {
+                    None => standing.drawn += 1,
+                    Some(winner) if winner == player => standing.won += 1,
+                    Some(_) => standing.lost += 1,
+                }
+            }
+        }
+        standings.sort_by_key(|standing| {
+            (
+                Reverse((
+                    standing.rounds_survived,
+                    standing.score,
+                    standing.won,
+                    standing.best_round,
+                )),
+                standing.player,
+            )
+        });
+        Self {
+            players,
+            matches,
+            standings,
+        }
+    }
+}
+
+impl Display for Tournament {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .players
+            .iter()
+            .map(|player| player.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("Player".len());
+        write!(
+            f,
+            "Rank  {:width$}  Played  Won  Drawn  Lost  Score  Best",
+            "Player"
+        )?;
+        for (rank, standing) in (1..).zip(&self.standings) {
+            write!(
+                f,
+                "\n{:>4}  {:width$}  {:>6}  {:>3}  {:>5}  {:>4}  {:>5}  {:>4}",
+                rank,
+                self.players[standing.player].name,
+                standing.played,
+                standing.won,
+                standing.drawn,
+                standing.lost,
+                standing.score,
+                standing.best_round
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players() -> Vec<Player> {
+        vec![
+            Player::parse("rock", "A X\nB X\n").unwrap(),
+            Player::parse("paper", "A Y\nC Y\n").unwrap(),
+            Player::parse("scissors", "A Z\nA Z\n").unwrap(),
+        ]
+    }
+
+    fn order(tournament: &Tournament) -> Vec<usize> {
+        tournament.standings.iter().map(|s| s.player).collect()
+    }
+
+    #[test]
+    fn round_robin() {
+        let tournament = Tournament::round_robin(players()).unwrap();
+        assert_eq!(3, tournament.matches.len());
+        assert_eq!([2, 16], tournament.matches[0].scores);
+        assert_eq!(vec![2, 1, 0], order(&tournament));
+        let scores: Vec<u32> = tournament.standings.iter().map(|s| s.score).collect();
+        assert_eq!(vec![24, 20, 16], scores);
+        assert!(tournament
+            .standings
+            .iter()
+            .all(|s| s.won == 1 && s.lost == 1));
+        assert_eq!(
+            "Rank  Player    Played  Won  Drawn  Lost  Score  Best\n   \
+             1  scissors       2    1      0     1     24     9",
+            tournament
+                .to_string()
+                .lines()
+                .take(2)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn single_elimination() {
+        let tournament = Tournament::single_elimination(players()).unwrap();
+        assert_eq!(2, tournament.matches.len());
+        assert_eq!(Some(1), tournament.matches[0].winner);
+        assert_eq!(Some(2), tournament.matches[1].winner);
+        assert_eq!(vec![2, 1, 0], order(&tournament));
+        assert_eq!(2, tournament.standings[0].rounds_survived);
+    }
+
+    #[test]
+    fn ties() {
+        let players = vec![
+            Player::parse("first", "A X\n").unwrap(),
+            Player::parse("second", "B X\n").unwrap(),
+        ];
+        let tournament = Tournament::round_robin(players.clone()).unwrap();
+        assert_eq!(None, tournament.matches[0].winner);
+        assert_eq!(vec![0, 1], order(&tournament));
+        let tournament = Tournament::single_elimination(players).unwrap();
+        assert_eq!(Some(0), tournament.matches[0].winner);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Some(Error::EmptyGuide {
+                name: "empty".into()
+            }),
+            Player::parse("empty", "").err()
+        );
+        assert_eq!(
+            Some(Error::NotEnoughPlayers { count: 1 }),
+            Tournament::round_robin(players()[..1].to_vec()).err()
+        );
+    }
+}
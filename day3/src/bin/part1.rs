@@ -1,23 +1,24 @@
-use anyhow::{Context, Result};
-use day3::Rucksack;
-use std::io::{self, Read};
+use std::{env, io};
+
+use anyhow::{Error, Result};
+use day3::{repack::Repacking, Rucksack};
+
+const USAGE: &str = "\
+Usage:
+    day3-part1 [--repack]
+
+Sums the priorities of the items found in both compartments of each rucksack. --repack instead
+plans the fewest swaps that leave every item type in a single compartment.";
 
 fn main() -> Result<()> {
-    let mut buffer = String::new();
-    let mut stdin = io::stdin().lock();
-    stdin.read_to_string(&mut buffer).context("reading stdin")?;
+    let args: Vec<String> = env::args().skip(1).collect();
+    let rucksacks = Rucksack::iter_from_reader(io::stdin().lock());
 
-    let mut total_priority = 0;
-    for line in buffer.lines() {
-        let rucksack_common_items = Rucksack::try_from(line)
-            .context("creating rucksack from input string")?
-            .common_items_between_compartments();
-        total_priority += rucksack_common_items
-            .iter()
-            .fold(0, |acc, item| acc + item.priority());
+    match args.as_slice() {
+        [] => println!("Total priority: {}", day3::total_priority(rucksacks)?),
+        [arg] if arg == "--repack" => println!("{}", Repacking::new(rucksacks)?),
+        _ => return Err(Error::msg(USAGE)),
     }
 
-    println!("Total priority: {total_priority}");
-
     Ok(())
 }
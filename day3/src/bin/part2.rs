@@ -1,36 +1,44 @@
-use anyhow::{Context, Result};
-use day3::Rucksack;
-use itertools::Itertools;
-use std::io::{self, Read};
+use std::{env, io};
 
-fn main() -> Result<()> {
-    let mut buffer = String::new();
-    let mut stdin = io::stdin().lock();
-    stdin.read_to_string(&mut buffer).context("reading stdin")?;
+use anyhow::{Context, Error, Result};
+use day3::{
+    groups::{GroupReport, Groups},
+    Rucksack, GROUP_SIZE,
+};
 
-    let mut total_priority = 0;
-    let mut intersection = Vec::with_capacity(3);
-    let mut count = 0;
-    for line in buffer.lines() {
-        let rucksack = Rucksack::try_from(line).context("rucksack from input")?;
-        if intersection.is_empty() {
-            intersection = rucksack.items();
-        } else {
-            intersection = rucksack.intersect(intersection);
-        }
-        count += 1;
+const USAGE: &str = "\
+Usage:
+    day3-part2 [--size N] [--report]
 
-        if count == 3 {
-            total_priority += intersection
-                .iter()
-                .unique()
-                .fold(0, |acc, e| acc + e.priority());
-            intersection.clear();
-            count = 0;
+Sums the priorities of the badges of each group of N elves (default 3). --report lists every
+group's badge or what's wrong with it instead of stopping at the first problem.";
+
+fn main() -> Result<()> {
+    let mut size = GROUP_SIZE;
+    let mut report = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                let n = args.next().context(USAGE)?;
+                size = n.parse().with_context(|| format!("{n} is not a valid N"))?;
+            }
+            "--report" => report = true,
+            _ => return Err(Error::msg(USAGE)),
         }
     }
 
-    println!("Total priority: {total_priority}");
+    let rucksacks = Rucksack::iter_from_reader(io::stdin().lock());
+    if report {
+        println!("{}", GroupReport::new(rucksacks, size)?);
+        return Ok(());
+    }
+
+    let mut total_priority = 0;
+    for group in Groups::new(rucksacks, size)? {
+        total_priority += group?.badge.priority();
+    }
+    println!("Total priority: {}", total_priority);
 
     Ok(())
 }
@@ -0,0 +1,228 @@
+use std::fmt::Display;
+
+use crate::{Error, Item, ItemSet, Rucksack};
+
+/// A group of elves and the one item type all of them carry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Group {
+    pub first_line: usize,
+    pub last_line: usize,
+    pub badge: Item,
+}
+
+/// Iterator over consecutive groups of `size` rucksacks, one rucksack per line.
+pub struct Groups<I> {
+    rucksacks: I,
+    size: usize,
+    line: usize,
+}
+
+impl<I> Groups<I>
+where
+    I: Iterator<Item = Result<Rucksack, Error>>,
+{
+    pub fn new(rucksacks: impl IntoIterator<IntoIter = I>, size: usize) -> Result<Self, Error> {
+        if size == 0 {
+            return Err(Error::ZeroGroupSize);
+        }
+        Ok(Self {
+            rucksacks: rucksacks.into_iter(),
+            size,
+            line: 0,
+        })
+    }
+}
+
+impl<I> Iterator for Groups<I>
+where
+    I: Iterator<Item = Result<Rucksack, Error>>,
+{
+    type Item = Result<Group, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first_line = self.line + 1;
+        let mut common = ItemSet::ALL;
+        let mut error = None;
+        for count in 0..self.size {
+            match self.rucksacks.next() {
+                None if count == 0 => return None,
+                None => {
+                    return Some(Err(Error::IncompleteGroup {
+                        size: self.size,
+                        got: count,
+                        line: first_line,
+                    }))
+                }
+                // Keep reading to the end of the group so the next one starts where it should.
+                Some(Err(e)) => error = error.or(Some(e)),
+                Some(Ok(rucksack)) => common = common.intersection(rucksack.items()),
+            }
+            self.line += 1;
+        }
+        if let Some(e) = error {
+            return Some(Err(e));
+        }
+
+        let last_line = self.line;
+        let mut badges = common.iter();
+        Some(match (badges.next(), badges.next()) {
+            (None, _) => Err(Error::NoBadge {
+                first_line,
+                last_line,
+            }),
+            (Some(badge), None) => Ok(Group {
+                first_line,
+                last_line,
+                badge,
+            }),
+            (Some(_), Some(_)) => Err(Error::MultipleBadges {
+                candidates: common,
+                first_line,
+                last_line,
+            }),
+        })
+    }
+}
+
+/// Finds the badge of each group of `size` elves, failing on the first group without exactly one.
+pub fn groups(input: &str, size: usize) -> Result<Vec<Group>, Error> {
+    Groups::new(Rucksack::iter_from_reader(input.as_bytes()), size)?.collect()
+}
+
+/// Every group's badge or what's wrong with it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GroupReport {
+    pub groups: Vec<Result<Group, Error>>,
+}
+
+impl GroupReport {
+    pub fn new(
+        rucksacks: impl IntoIterator<Item = Result<Rucksack, Error>>,
+        size: usize,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            groups: Groups::new(rucksacks, size)?.collect(),
+        })
+    }
+
+    pub fn problems(&self) -> usize {
+        self.groups.iter().filter(|group| group.is_err()).count()
+    }
+
+    /// Sum of the priorities of the badges that could be found.
+    pub fn total_priority(&self) -> u32 {
+        self.groups
+            .iter()
+            .flatten()
+            .map(|group| group.badge.priority())
+            .sum()
+    }
+}
+
+impl Display for GroupReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for group in &self.groups {
+            match group {
+                Ok(group) => writeln!(
+                    f,
+                    "lines {}-{}: badge {} (priority {})",
+                    group.first_line,
+                    group.last_line,
+                    group.badge,
+                    group.badge.priority()
+                )?,
+                Err(e) => writeln!(f, "{e}")?,
+            }
+        }
+        write!(
+            f,
+            "{} groups, {} with problems, total badge priority {}",
+            self.groups.len(),
+            self.problems(),
+            self.total_priority()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    #[test]
+    fn example() {
+        let badges: Vec<Item> = groups(EXAMPLE, 3)
+            .unwrap()
+            .iter()
+            .map(|group| group.badge)
+            .collect();
+        assert_eq!(vec![Item('r'), Item('Z')], badges);
+        assert_eq!(
+            Group {
+                first_line: 4,
+                last_line: 6,
+                badge: Item('Z')
+            },
+            groups(EXAMPLE, 3).unwrap()[1]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Some(Error::ZeroGroupSize), groups(EXAMPLE, 0).err());
+        assert_eq!(
+            Some(Error::IncompleteGroup {
+                size: 2,
+                got: 1,
+                line: 3
+            }),
+            groups("aa\naa\naa\n", 2).err()
+        );
+        assert_eq!(
+            Some(Error::MultipleBadges {
+                candidates: "abc".chars().map(Item).collect(),
+                first_line: 1,
+                last_line: 2
+            }),
+            groups("abcd\ncbax\n", 2).err()
+        );
+        assert_eq!(
+            Some(Error::NoBadge {
+                first_line: 3,
+                last_line: 4
+            }),
+            groups("aa\naa\nab\ncd\n", 2).err()
+        );
+    }
+
+    #[test]
+    fn report() {
+        let input = "aa\naa\nab\ncd\nx1\nxy\nxy\n";
+        let report = GroupReport::new(Rucksack::iter_from_reader(input.as_bytes()), 2).unwrap();
+        assert_eq!(4, report.groups.len());
+        assert_eq!(3, report.problems());
+        assert_eq!(
+            Some(&Error::InvalidItem {
+                ch: '1',
+                line: 5,
+                col: 2
+            }),
+            report.groups[2].as_ref().err()
+        );
+        assert_eq!(
+            "lines 1-2: badge a (priority 1)\n\
+             lines 3-4: no item is carried by every elf in the group\n\
+             line 5, column 2: '1' is not a valid item, items can only be a-z or A-Z\n\
+             line 7: the last group has only 1 of 2 rucksacks\n\
+             4 groups, 3 with problems, total badge priority 1",
+            report.to_string()
+        );
+    }
+}
@@ -1,67 +1,166 @@
-use anyhow::Result;
-use itertools::Itertools;
+use std::{
+    fmt::Display,
+    io::{self, BufRead},
+};
 
-#[derive(Debug)]
-pub struct Rucksack(Vec<Item>, Vec<Item>);
+use common::Solver;
+use groups::Groups;
+use thiserror::Error;
+
+pub mod groups;
+pub mod repack;
+
+/// Number of elves sharing a badge in the puzzle.
+pub const GROUP_SIZE: usize = 3;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("{ch:?} is not a valid item, items can only be a-z or A-Z")]
+    NotAnItem { ch: char },
+    #[error("rucksack must contain an even amount of items, got {len}")]
+    UnevenRucksack { len: usize },
+    #[error("line {line}, column {col}: {ch:?} is not a valid item, items can only be a-z or A-Z")]
+    InvalidItem { ch: char, line: usize, col: usize },
+    #[error("line {line}: rucksack must contain an even amount of items, got {len}")]
+    OddItemCount { len: usize, line: usize },
+    #[error("line {line}: failed to read input: {kind}")]
+    Io { kind: io::ErrorKind, line: usize },
+    #[error("group size must be at least 1")]
+    ZeroGroupSize,
+    #[error("lines {first_line}-{last_line}: no item is carried by every elf in the group")]
+    NoBadge { first_line: usize, last_line: usize },
+    #[error(
+        "lines {first_line}-{last_line}: group shares {candidates}, so its badge is ambiguous"
+    )]
+    MultipleBadges {
+        candidates: ItemSet,
+        first_line: usize,
+        last_line: usize,
+    },
+    #[error("line {line}: the last group has only {got} of {size} rucksacks")]
+    IncompleteGroup {
+        size: usize,
+        got: usize,
+        line: usize,
+    },
+}
+
+/// The items in a rucksack, split evenly between its two compartments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rucksack {
+    items: Vec<Item>,
+    /// The item types in each compartment.
+    types: [ItemSet; 2],
+}
+
+impl Rucksack {
+    pub fn common_items_between_compartments(&self) -> ItemSet {
+        self.types[0].intersection(self.types[1])
+    }
+
+    pub fn items(&self) -> ItemSet {
+        self.types[0].union(self.types[1])
+    }
+
+    pub fn compartments(&self) -> (&[Item], &[Item]) {
+        self.items.split_at(self.items.len() / 2)
+    }
+}
 
 impl Rucksack {
-    pub fn common_items_between_compartments(&self) -> Vec<Item> {
-        self.0
-            .clone()
-            .into_iter()
-            .filter(|&item| self.1.contains(&item))
-            .unique()
-            .collect()
+    fn parse(value: &str, line: usize) -> Result<Self, Error> {
+        let items = value
+            .chars()
+            .enumerate()
+            .map(|(index, ch)| {
+                Item::try_from(ch).map_err(|_| Error::InvalidItem {
+                    ch,
+                    line,
+                    col: index + 1,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let len = items.len();
+        Self::from_items(items).ok_or(Error::OddItemCount { len, line })
     }
 
-    pub fn items(&self) -> Vec<Item> {
-        let mut all_items = self.0.clone();
-        all_items.append(&mut self.1.clone());
-        all_items
+    /// Splits the items between the compartments, or `None` if they can't be split evenly.
+    fn from_items(items: Vec<Item>) -> Option<Self> {
+        if !items.len().is_multiple_of(2) {
+            return None;
+        }
+
+        let (compartment_a, compartment_b) = items.split_at(items.len() / 2);
+        let types = [
+            compartment_a.iter().copied().collect(),
+            compartment_b.iter().copied().collect(),
+        ];
+        Some(Rucksack { items, types })
     }
 
-    pub fn intersect(&self, other: Vec<Item>) -> Vec<Item> {
-        self.items()
-            .into_iter()
-            .filter(|item| other.contains(item)).collect()
+    /// Reads one rucksack per line, so only the current line is held in memory.
+    pub fn iter_from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Self, Error>> {
+        (1..).zip(reader.lines()).map(|(line, rucksack)| {
+            let rucksack = rucksack.map_err(|e| Error::Io {
+                kind: e.kind(),
+                line,
+            })?;
+            Self::parse(&rucksack, line)
+        })
     }
 }
 
 impl TryFrom<&str> for Rucksack {
-    type Error = anyhow::Error;
+    type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if value.len() % 2 != 0 {
-            return Err(anyhow::Error::msg(
-                "rucksack must contain an even amount of items",
-            ));
-        }
+        let items = value
+            .chars()
+            .map(Item::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let len = items.len();
+        Self::from_items(items).ok_or(Error::UnevenRucksack { len })
+    }
+}
 
-        let mut compartment_a = Vec::with_capacity(value.len() / 2);
-        let mut compartment_b = Vec::with_capacity(value.len() / 2);
+/// Parses one rucksack per line, reporting errors against their line in `input`.
+pub fn parse_rucksacks(input: &str) -> Result<Vec<Rucksack>, Error> {
+    Rucksack::iter_from_reader(input.as_bytes()).collect()
+}
 
-        let (str_a, str_b) = value.split_at(value.len() / 2);
-        for c in str_a.chars() {
-            compartment_a.push(Item::try_from(c)?);
-        }
-        for c in str_b.chars() {
-            compartment_b.push(Item::try_from(c)?);
-        }
+/// Sum of the priorities of the items found in both compartments of each rucksack.
+pub fn total_priority(
+    rucksacks: impl IntoIterator<Item = Result<Rucksack, Error>>,
+) -> Result<u32, Error> {
+    let mut total_priority = 0;
+    for rucksack in rucksacks {
+        total_priority += rucksack?.common_items_between_compartments().priority_sum();
+    }
+    Ok(total_priority)
+}
 
-        Ok(Rucksack(compartment_a, compartment_b))
+/// Sum of the priorities of the badges carried by each group of three elves.
+pub fn total_badge_priority(
+    rucksacks: impl IntoIterator<Item = Result<Rucksack, Error>>,
+) -> Result<u32, Error> {
+    let mut total_priority = 0;
+    for group in Groups::new(rucksacks, GROUP_SIZE)? {
+        total_priority += group?.badge.priority();
     }
+    Ok(total_priority)
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct Item(char);
 
 impl TryFrom<char> for Item {
-    type Error = anyhow::Error;
+    type Error = Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             'a'..='z' | 'A'..='Z' => Ok(Self(value)),
-            _ => Err(anyhow::Error::msg("items can only be a-z or A-Z")),
+            ch => Err(Error::NotAnItem { ch }),
         }
     }
 }
@@ -76,6 +175,123 @@ impl Item {
             _ => unreachable!("items can only be a-z or A-Z"),
         }
     }
+
+    fn from_priority(priority: u32) -> Self {
+        match priority {
+            1..=26 => Self(char::from(b'a' + (priority - 1) as u8)),
+            27..=52 => Self(char::from(b'A' + (priority - 27) as u8)),
+            _ => unreachable!("priorities can only be 1-52"),
+        }
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A set of item types, stored as one bit per type at `priority - 1`.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: Self = Self(0);
+    pub const ALL: Self = Self((1 << 52) - 1);
+
+    fn bit(item: Item) -> u64 {
+        1 << (item.priority() - 1)
+    }
+
+    /// Adds `item`, returning whether it wasn't already in the set.
+    pub fn insert(&mut self, item: Item) -> bool {
+        let added = !self.contains(item);
+        self.0 |= Self::bit(item);
+        added
+    }
+
+    /// Removes `item`, returning whether it was in the set.
+    pub fn remove(&mut self, item: Item) -> bool {
+        let removed = self.contains(item);
+        self.0 &= !Self::bit(item);
+        removed
+    }
+
+    pub fn contains(&self, item: Item) -> bool {
+        self.0 & Self::bit(item) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Items in order of priority.
+    pub fn iter(&self) -> impl Iterator<Item = Item> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let priority = bits.trailing_zeros() + 1;
+            bits &= bits - 1;
+            Some(Item::from_priority(priority))
+        })
+    }
+
+    pub fn priority_sum(&self) -> u32 {
+        self.iter().map(|item| item.priority()).sum()
+    }
+}
+
+impl Display for ItemSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.iter().try_for_each(|item| write!(f, "{item}"))
+    }
+}
+
+impl FromIterator<Item> for ItemSet {
+    fn from_iter<T: IntoIterator<Item = Item>>(items: T) -> Self {
+        let mut set = Self::EMPTY;
+        for item in items {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn part1(input: &str) -> anyhow::Result<u32> {
+        Ok(total_priority(Rucksack::iter_from_reader(
+            input.as_bytes(),
+        ))?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<u32> {
+        Ok(total_badge_priority(Rucksack::iter_from_reader(
+            input.as_bytes(),
+        ))?)
+    }
 }
 
 #[cfg(test)]
@@ -94,32 +310,74 @@ mod tests {
     fn rucksack() {
         let rucksack = Rucksack::try_from("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
         let common_items = rucksack.common_items_between_compartments();
-        assert_eq!(1, common_items.len());
-        assert_eq!('p', common_items[0].0);
+        assert_eq!(vec![Item('p')], common_items.iter().collect::<Vec<_>>());
 
         let rucksack = Rucksack::try_from("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL").unwrap();
         let common_items = rucksack.common_items_between_compartments();
-        assert_eq!(1, common_items.len());
-        assert_eq!('L', common_items[0].0);
+        assert_eq!(vec![Item('L')], common_items.iter().collect::<Vec<_>>());
 
         let rucksack = Rucksack::try_from("PmmdzqPrVvPwwTWBwg").unwrap();
         let common_items = rucksack.common_items_between_compartments();
-        assert_eq!(1, common_items.len());
-        assert_eq!('P', common_items[0].0);
+        assert_eq!(vec![Item('P')], common_items.iter().collect::<Vec<_>>());
 
         let rucksack = Rucksack::try_from("wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn").unwrap();
         let common_items = rucksack.common_items_between_compartments();
-        assert_eq!(1, common_items.len());
-        assert_eq!('v', common_items[0].0);
+        assert_eq!(vec![Item('v')], common_items.iter().collect::<Vec<_>>());
 
         let rucksack = Rucksack::try_from("ttgJtRGJQctTZtZT").unwrap();
         let common_items = rucksack.common_items_between_compartments();
-        assert_eq!(1, common_items.len());
-        assert_eq!('t', common_items[0].0);
+        assert_eq!(vec![Item('t')], common_items.iter().collect::<Vec<_>>());
 
         let rucksack = Rucksack::try_from("CrZsJsPPZsGzwwsLwLmpwMDw").unwrap();
         let common_items = rucksack.common_items_between_compartments();
-        assert_eq!(1, common_items.len());
-        assert_eq!('s', common_items[0].0);
+        assert_eq!(vec![Item('s')], common_items.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn item_set() {
+        let set = |items: &str| -> ItemSet { items.chars().map(Item).collect() };
+        let a = set("abcZ");
+        let b = set("cZzA");
+        assert_eq!(set("abcAzZ"), a.union(b));
+        assert_eq!(set("cZ"), a.intersection(b));
+        assert_eq!(set("ab"), a.difference(b));
+        assert_eq!(
+            vec![Item('a'), Item('b'), Item('c'), Item('Z')],
+            a.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(1 + 2 + 3 + 52, a.priority_sum());
+        assert_eq!(52, ItemSet::ALL.len());
+        assert_eq!((1..=52).sum::<u32>(), ItemSet::ALL.priority_sum());
+
+        let mut items = ItemSet::EMPTY;
+        assert!(items.insert(Item('q')));
+        assert!(!items.insert(Item('q')));
+        assert!(items.contains(Item('q')));
+        assert!(items.remove(Item('q')));
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Some(Error::InvalidItem {
+                ch: '1',
+                line: 2,
+                col: 3
+            }),
+            parse_rucksacks("abab\nab1b\n").err()
+        );
+        assert_eq!(
+            Some(Error::OddItemCount { len: 3, line: 1 }),
+            parse_rucksacks("abc\n").err()
+        );
+        assert_eq!(
+            Some(Error::NotAnItem { ch: '1' }),
+            Rucksack::try_from("ab1b").err()
+        );
+        assert_eq!(
+            Some(Error::UnevenRucksack { len: 3 }),
+            Rucksack::try_from("abc").err()
+        );
     }
 }
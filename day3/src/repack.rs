@@ -0,0 +1,230 @@
+use std::fmt::Display;
+
+use crate::{Error, Item, Rucksack};
+
+/// Exchanges an item in the first compartment with one in the second.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Swap {
+    pub from_first: Item,
+    pub from_second: Item,
+}
+
+/// The swaps that leave no item type in both compartments of a rucksack.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Plan {
+    pub swaps: Vec<Swap>,
+}
+
+impl Plan {
+    /// Number of items that change compartment, two per swap.
+    pub fn moves(&self) -> usize {
+        self.swaps.len() * 2
+    }
+
+    /// Sum of the priorities of the items that change compartment.
+    pub fn priority(&self) -> u32 {
+        self.swaps
+            .iter()
+            .map(|swap| swap.from_first.priority() + swap.from_second.priority())
+            .sum()
+    }
+}
+
+/// Cost of the cheapest way found so far: items moved, then their total priority.
+type Cost = (u32, u32);
+
+/// Finds the fewest swaps, and among those the lowest total priority, that leave every item type
+/// in a single compartment. Returns `None` if no set of item types fills exactly half the
+/// rucksack.
+///
+/// Each item type goes wholly into one compartment, costing the items of that type currently in
+/// the other one, so this is a subset sum over the item types solved one type at a time.
+pub fn plan(rucksack: &Rucksack) -> Option<Plan> {
+    let (first, second) = rucksack.compartments();
+    let half = first.len();
+    let mut counts = [[0usize; 2]; 52];
+    for item in first {
+        counts[item.priority() as usize - 1][0] += 1;
+    }
+    for item in second {
+        counts[item.priority() as usize - 1][1] += 1;
+    }
+    let types: Vec<(Item, [usize; 2])> = (1..=52)
+        .map(Item::from_priority)
+        .zip(counts)
+        .filter(|(_, [a, b])| a + b > 0)
+        .collect();
+
+    // costs[k][size] is the cheapest way to put `size` items in the first compartment using only
+    // the first `k` item types.
+    let mut costs: Vec<Vec<Option<Cost>>> = vec![vec![None; half + 1]];
+    costs[0][0] = Some((0, 0));
+    for (item, [a, b]) in &types {
+        let previous = costs.last().expect("costs starts with a row");
+        let mut next = vec![None; half + 1];
+        for (size, cost) in previous.iter().enumerate() {
+            let Some((moves, priority)) = *cost else {
+                continue;
+            };
+            let options = [
+                (
+                    size,
+                    (moves + *a as u32, priority + *a as u32 * item.priority()),
+                ),
+                (
+                    size + a + b,
+                    (moves + *b as u32, priority + *b as u32 * item.priority()),
+                ),
+            ];
+            for (size, cost) in options {
+                if size <= half && next[size].is_none_or(|best| cost < best) {
+                    next[size] = Some(cost);
+                }
+            }
+        }
+        costs.push(next);
+    }
+    costs.last()?[half]?;
+
+    // Walk back through the table to see where each item type went.
+    let mut to_second = Vec::new();
+    let mut to_first = Vec::new();
+    let mut size = half;
+    for (k, &(item, [a, b])) in types.iter().enumerate().rev() {
+        let best = costs[k + 1][size];
+        let kept_in_first = size >= a + b
+            && costs[k][size - a - b]
+                .map(|(moves, priority)| (moves + b as u32, priority + b as u32 * item.priority()))
+                == best;
+        if kept_in_first {
+            to_first.extend(std::iter::repeat_n(item, b));
+            size -= a + b;
+        } else {
+            to_second.extend(std::iter::repeat_n(item, a));
+        }
+    }
+
+    Some(Plan {
+        swaps: to_second
+            .into_iter()
+            .rev()
+            .zip(to_first.into_iter().rev())
+            .map(|(from_first, from_second)| Swap {
+                from_first,
+                from_second,
+            })
+            .collect(),
+    })
+}
+
+/// The plan for every rucksack in a file, by line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Repacking {
+    pub plans: Vec<(usize, Option<Plan>)>,
+}
+
+impl Repacking {
+    pub fn new(
+        rucksacks: impl IntoIterator<Item = Result<Rucksack, Error>>,
+    ) -> Result<Self, Error> {
+        let plans = (1..)
+            .zip(rucksacks)
+            .map(|(line, rucksack)| Ok((line, plan(&rucksack?))))
+            .collect::<Result<_, Error>>()?;
+        Ok(Self { plans })
+    }
+
+    fn possible(&self) -> impl Iterator<Item = &Plan> {
+        self.plans.iter().filter_map(|(_, plan)| plan.as_ref())
+    }
+
+    /// Lines of the rucksacks that can't be repacked.
+    pub fn impossible(&self) -> Vec<usize> {
+        self.plans
+            .iter()
+            .filter(|(_, plan)| plan.is_none())
+            .map(|&(line, _)| line)
+            .collect()
+    }
+
+    pub fn moves(&self) -> usize {
+        self.possible().map(Plan::moves).sum()
+    }
+
+    pub fn priority(&self) -> u32 {
+        self.possible().map(Plan::priority).sum()
+    }
+}
+
+impl Display for Repacking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (line, plan) in &self.plans {
+            write!(f, "line {line}: ")?;
+            match plan {
+                None => writeln!(f, "cannot be repacked")?,
+                Some(plan) if plan.swaps.is_empty() => writeln!(f, "already packed")?,
+                Some(plan) => {
+                    let swaps: Vec<String> = plan
+                        .swaps
+                        .iter()
+                        .map(|swap| format!("{} <-> {}", swap.from_first, swap.from_second))
+                        .collect();
+                    writeln!(f, "{}", swaps.join(", "))?;
+                }
+            }
+        }
+        write!(
+            f,
+            "{} moves, total priority {}, {} rucksacks cannot be repacked",
+            self.moves(),
+            self.priority(),
+            self.impossible().len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan_for(rucksack: &str) -> Option<Plan> {
+        plan(&Rucksack::try_from(rucksack).unwrap())
+    }
+
+    #[test]
+    fn plans() {
+        // Swapping the first compartment's p for the second's c is cheaper than bringing the
+        // second p over in exchange for g.
+        let plan = plan_for("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+        assert_eq!(
+            vec![Swap {
+                from_first: Item('p'),
+                from_second: Item('c')
+            }],
+            plan.swaps
+        );
+        assert_eq!(2, plan.moves());
+        assert_eq!(16 + 3, plan.priority());
+
+        assert_eq!(Some(Plan::default()), plan_for("abcd"));
+        assert_eq!(2, plan_for("abab").unwrap().moves());
+        assert_eq!(None, plan_for("aaab"));
+    }
+
+    #[test]
+    fn repacking() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\naaab\nabcd\nabab\n";
+        let repacking = Repacking::new(Rucksack::iter_from_reader(input.as_bytes())).unwrap();
+        assert_eq!(vec![2], repacking.impossible());
+        assert_eq!(4, repacking.moves());
+        assert_eq!(19 + 1 + 2, repacking.priority());
+        assert_eq!(
+            "line 1: p <-> c\n\
+             line 2: cannot be repacked\n\
+             line 3: already packed\n\
+             line 4: a <-> b\n\
+             4 moves, total priority 22, 1 rucksacks cannot be repacked",
+            repacking.to_string()
+        );
+    }
+}
@@ -1,32 +1,49 @@
-use anyhow::{Context, Result};
-use day4::Assignment;
-use std::io::{self, Read};
+use std::{env, io};
+
+use anyhow::{Error, Result};
+use day4::interval::busiest_sections;
+
+const USAGE: &str = "\
+Usage:
+    day4-part1 [--busiest]
+
+Counts the pairs in which one assignment fully contains the other. --busiest instead reads any
+number of assignments per line and finds the sections assigned to the most elves.";
 
 fn main() -> Result<()> {
-    let mut buffer = String::new();
-    let mut stdin = io::stdin().lock();
-    stdin
-        .read_to_string(&mut buffer)
-        .context("reading stdin to string")?;
-    let mut overlaps = 0;
-    for line in buffer.lines() {
-        let (a, b) = line
-            .split_once(',')
-            .context("splitting into two section ID ranges")?;
-        let assignment_a = Assignment::try_from(
-            a.split_once('-')
-                .context("splitting into low and high IDs")?,
-        )
-        .context("creating assignment from ranges")?;
-        let assignment_b = Assignment::try_from(
-            b.split_once('-')
-                .context("splitting into low and high IDs")?,
-        )
-        .context("creating assignment from ranges")?;
-        if assignment_a.fully_overlaps(&assignment_b) {
-            overlaps += 1;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.as_slice() {
+        [] => {
+            let pairs = day4::pairs_from_reader(io::stdin().lock());
+            println!(
+                "Number of fully overlapping assignments: {}",
+                day4::count_fully_overlapping(pairs)?
+            );
+        }
+        [arg] if arg == "--busiest" => {
+            let mut assignments = Vec::new();
+            for line in day4::assignments_from_reader(io::stdin().lock()) {
+                assignments.extend(line?);
+            }
+            match busiest_sections(&assignments) {
+                None => println!("No sections are assigned"),
+                Some(busiest) => {
+                    let sections: Vec<String> = busiest
+                        .sections
+                        .ranges()
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect();
+                    println!(
+                        "At most {} elves are assigned to sections {}",
+                        busiest.elves,
+                        sections.join(", ")
+                    );
+                }
+            }
         }
+        _ => return Err(Error::msg(USAGE)),
     }
-    println!("Number of fully overlapping assignments: {overlaps}");
     Ok(())
 }
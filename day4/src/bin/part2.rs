@@ -1,32 +1,26 @@
-use anyhow::{Context, Result};
-use day4::Assignment;
-use std::io::{self, Read};
+use std::{env, io};
+
+use anyhow::{Error, Result};
+use day4::report::CoverageReport;
+
+const USAGE: &str = "\
+Usage:
+    day4-part2 [--report]
+
+Counts the pairs whose assignments overlap. --report instead shows how the assignments in each
+pair relate, with counts of each kind of relationship.";
 
 fn main() -> Result<()> {
-    let mut buffer = String::new();
-    let mut stdin = io::stdin().lock();
-    stdin
-        .read_to_string(&mut buffer)
-        .context("reading stdin to string")?;
-    let mut overlaps = 0;
-    for line in buffer.lines() {
-        let (a, b) = line
-            .split_once(',')
-            .context("splitting into two section ID ranges")?;
-        let assignment_a = Assignment::try_from(
-            a.split_once('-')
-                .context("splitting into low and high IDs")?,
-        )
-        .context("creating assignment from ranges")?;
-        let assignment_b = Assignment::try_from(
-            b.split_once('-')
-                .context("splitting into low and high IDs")?,
-        )
-        .context("creating assignment from ranges")?;
-        if assignment_a.overlaps(&assignment_b) {
-            overlaps += 1;
-        }
+    let args: Vec<String> = env::args().skip(1).collect();
+    let pairs = day4::pairs_from_reader(io::stdin().lock());
+
+    match args.as_slice() {
+        [] => println!(
+            "Number of overlapping assignments: {}",
+            day4::count_overlapping(pairs)?
+        ),
+        [arg] if arg == "--report" => println!("{}", CoverageReport::new(pairs)?),
+        _ => return Err(Error::msg(USAGE)),
     }
-    println!("Number of overlapping assignments: {overlaps}");
     Ok(())
 }
@@ -0,0 +1,300 @@
+use crate::{Assignment, Error};
+
+impl Assignment {
+    pub fn low(&self) -> u32 {
+        self.0
+    }
+
+    pub fn high(&self) -> u32 {
+        self.1
+    }
+
+    /// Length of the assignment in sections, which is never zero.
+    pub fn sections(&self) -> u64 {
+        u64::from(self.1 - self.0) + 1
+    }
+
+    pub fn contains(&self, section: u32) -> bool {
+        self.0 <= section && section <= self.1
+    }
+
+    /// Sections in both assignments, if there are any.
+    pub fn intersection(&self, other: &Assignment) -> Option<Assignment> {
+        let low = self.0.max(other.0);
+        let high = self.1.min(other.1);
+        (low <= high).then_some(Self(low, high))
+    }
+
+    /// Sections in either assignment: one range if they overlap or are next to each other, and
+    /// both in order otherwise.
+    pub fn union(&self, other: &Assignment) -> Vec<Assignment> {
+        let (first, second) = if self <= other {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if u64::from(second.0) <= u64::from(first.1) + 1 {
+            vec![Self(first.0, first.1.max(second.1))]
+        } else {
+            vec![*first, *second]
+        }
+    }
+
+    /// Sections in this assignment but not the other: none, one range, or the two ranges either
+    /// side of `other`.
+    pub fn difference(&self, other: &Assignment) -> Vec<Assignment> {
+        let Some(common) = self.intersection(other) else {
+            return vec![*self];
+        };
+        let mut difference = Vec::with_capacity(2);
+        if self.0 < common.0 {
+            difference.push(Self(self.0, common.0 - 1));
+        }
+        if common.1 < self.1 {
+            difference.push(Self(common.1 + 1, self.1));
+        }
+        difference
+    }
+}
+
+impl TryFrom<(u32, u32)> for Assignment {
+    type Error = Error;
+
+    fn try_from((low, high): (u32, u32)) -> Result<Self, Self::Error> {
+        if low > high {
+            return Err(Error::ReversedBounds { low, high });
+        }
+        Ok(Self(low, high))
+    }
+}
+
+/// Sections covered by any number of assignments, kept as sorted ranges that neither overlap
+/// nor touch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet(Vec<Assignment>);
+
+impl IntervalSet {
+    pub fn ranges(&self) -> &[Assignment] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of sections in the set.
+    pub fn sections(&self) -> u64 {
+        self.0.iter().map(Assignment::sections).sum()
+    }
+
+    pub fn contains(&self, section: u32) -> bool {
+        let index = self.0.partition_point(|range| range.1 < section);
+        self.0
+            .get(index)
+            .is_some_and(|range| range.contains(section))
+    }
+
+    /// Sections of `within` that no range in the set covers.
+    pub fn complement(&self, within: &Assignment) -> IntervalSet {
+        let mut gaps = vec![*within];
+        for range in &self.0 {
+            let Some(last) = gaps.pop() else {
+                break;
+            };
+            gaps.extend(last.difference(range));
+        }
+        IntervalSet(gaps)
+    }
+
+    /// Sections assigned to at least `k` of the assignments. Every section counts as covered at
+    /// least zero times, so `k = 0` gives the span from the lowest to the highest section.
+    pub fn covered_by_at_least<'a>(
+        assignments: impl IntoIterator<Item = &'a Assignment>,
+        k: usize,
+    ) -> IntervalSet {
+        let assignments: Vec<&Assignment> = assignments.into_iter().collect();
+        if k == 0 {
+            let low = assignments.iter().map(|a| a.0).min();
+            let high = assignments.iter().map(|a| a.1).max();
+            return low
+                .zip(high)
+                .map(|(low, high)| Assignment(low, high))
+                .into_iter()
+                .collect();
+        }
+        coverage(assignments)
+            .filter(|segment| segment.count >= k)
+            .map(|segment| segment.range)
+            .collect()
+    }
+}
+
+impl FromIterator<Assignment> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Assignment>>(assignments: T) -> Self {
+        let mut assignments: Vec<Assignment> = assignments.into_iter().collect();
+        assignments.sort_unstable();
+        let mut merged: Vec<Assignment> = Vec::with_capacity(assignments.len());
+        for assignment in assignments {
+            match merged.last_mut() {
+                Some(last) if u64::from(assignment.0) <= u64::from(last.1) + 1 => {
+                    last.1 = last.1.max(assignment.1);
+                }
+                _ => merged.push(assignment),
+            }
+        }
+        IntervalSet(merged)
+    }
+}
+
+/// The most elves assigned to any one section, and every section assigned to that many.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Busiest {
+    pub elves: usize,
+    pub sections: IntervalSet,
+}
+
+/// Finds the sections assigned to the most elves with a single sweep over the sorted start and
+/// end points, in O(n log n). Returns `None` if there are no assignments.
+pub fn busiest_sections<'a>(
+    assignments: impl IntoIterator<Item = &'a Assignment>,
+) -> Option<Busiest> {
+    let mut elves = 0;
+    let mut ranges = Vec::new();
+    for segment in coverage(assignments) {
+        if segment.count > elves {
+            elves = segment.count;
+            ranges.clear();
+        }
+        if segment.count == elves {
+            ranges.push(segment.range);
+        }
+    }
+    (elves > 0).then(|| Busiest {
+        elves,
+        sections: ranges.into_iter().collect(),
+    })
+}
+
+/// A range of sections all assigned to the same number of elves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Segment {
+    pub range: Assignment,
+    pub count: usize,
+}
+
+/// Splits the sections covered by `assignments` into segments of constant coverage, in order, by
+/// sweeping over the sorted start and end points.
+pub(crate) fn coverage<'a>(
+    assignments: impl IntoIterator<Item = &'a Assignment>,
+) -> impl Iterator<Item = Segment> {
+    // Ends are stored one past the last section, which may not fit in a u32.
+    let mut events: Vec<(u64, isize)> = assignments
+        .into_iter()
+        .flat_map(|a| [(u64::from(a.0), 1), (u64::from(a.1) + 1, -1)])
+        .collect();
+    events.sort_unstable();
+
+    let mut count = 0isize;
+    let mut events = events.into_iter().peekable();
+    std::iter::from_fn(move || loop {
+        let (position, change) = events.next()?;
+        count += change;
+        let &(next, _) = events.peek()?;
+        if next > position && count > 0 {
+            return Some(Segment {
+                range: Assignment(position as u32, (next - 1) as u32),
+                count: count as usize,
+            });
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn a(low: u32, high: u32) -> Assignment {
+        Assignment::try_from((low, high)).unwrap()
+    }
+
+    #[test]
+    fn algebra() {
+        assert_eq!(3, a(2, 4).sections());
+        assert!(a(2, 4).contains(4));
+        assert!(!a(2, 4).contains(5));
+
+        assert_eq!(Some(a(3, 4)), a(1, 4).intersection(&a(3, 6)));
+        assert_eq!(None, a(1, 2).intersection(&a(3, 4)));
+
+        assert_eq!(vec![a(1, 6)], a(3, 6).union(&a(1, 4)));
+        assert_eq!(vec![a(1, 4)], a(1, 2).union(&a(3, 4)));
+        assert_eq!(vec![a(1, 2), a(4, 5)], a(4, 5).union(&a(1, 2)));
+
+        assert_eq!(vec![a(1, 2), a(5, 6)], a(1, 6).difference(&a(3, 4)));
+        assert_eq!(vec![a(1, 2)], a(1, 4).difference(&a(3, 6)));
+        assert_eq!(Vec::<Assignment>::new(), a(3, 4).difference(&a(1, 6)));
+        assert_eq!(vec![a(1, 2)], a(1, 2).difference(&a(3, 4)));
+
+        assert_eq!(u64::from(u32::MAX) + 1, a(0, u32::MAX).sections());
+        assert_eq!(
+            Some(Error::ReversedBounds { low: 2, high: 1 }),
+            Assignment::try_from((2, 1)).err()
+        );
+    }
+
+    #[test]
+    fn interval_set() {
+        let assignments = [a(2, 4), a(6, 8), a(2, 3), a(4, 5), a(12, 13), a(3, 7)];
+        let set: IntervalSet = assignments.iter().copied().collect();
+        assert_eq!(&[a(2, 8), a(12, 13)], set.ranges());
+        assert_eq!(9, set.sections());
+        assert!(set.contains(8));
+        assert!(!set.contains(9));
+        assert!(set.contains(12));
+
+        assert_eq!(
+            &[a(1, 1), a(9, 11), a(14, 15)],
+            set.complement(&a(1, 15)).ranges()
+        );
+        assert_eq!(&[a(9, 11)], set.complement(&a(5, 12)).ranges());
+
+        assert_eq!(
+            &[a(2, 8), a(12, 13)],
+            IntervalSet::covered_by_at_least(&assignments, 1).ranges()
+        );
+        assert_eq!(
+            &[a(2, 7)],
+            IntervalSet::covered_by_at_least(&assignments, 2).ranges()
+        );
+        assert_eq!(
+            &[a(3, 4)],
+            IntervalSet::covered_by_at_least(&assignments, 3).ranges()
+        );
+        assert!(IntervalSet::covered_by_at_least(&assignments, 4).is_empty());
+        assert_eq!(
+            &[a(2, 13)],
+            IntervalSet::covered_by_at_least(&assignments, 0).ranges()
+        );
+        assert!(IntervalSet::covered_by_at_least(&[], 0).is_empty());
+    }
+
+    #[test]
+    fn busiest() {
+        let assignments: Vec<Assignment> =
+            crate::parse_assignments("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n")
+                .unwrap()
+                .into_iter()
+                .flatten()
+                .collect();
+        let busiest = busiest_sections(&assignments).unwrap();
+        assert_eq!(8, busiest.elves);
+        assert_eq!(&[a(6, 6)], busiest.sections.ranges());
+
+        // Elves leaving and joining at the same section leave the count unchanged.
+        let busiest = busiest_sections(&[a(1, 3), a(1, 5), a(4, 5), a(9, 10), a(10, 12)]).unwrap();
+        assert_eq!(2, busiest.elves);
+        assert_eq!(&[a(1, 5), a(10, 10)], busiest.sections.ranges());
+
+        assert_eq!(None, busiest_sections(&[]));
+    }
+}
@@ -1,26 +1,279 @@
-use anyhow::{Context, Error};
+use std::{
+    fmt::Display,
+    io::{self, BufRead},
+};
+
+use common::Solver;
+use thiserror::Error;
+
+pub mod interval;
+pub mod report;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("{token:?} is not a valid section ID")]
+    NotASectionId { token: String },
+    #[error("low section ID {low} is greater than high section ID {high}")]
+    ReversedBounds { low: u32, high: u32 },
+    #[error("line {line}, column {col}: {token:?} is not a valid section ID")]
+    InvalidSectionId {
+        token: String,
+        line: usize,
+        col: usize,
+    },
+    #[error("line {line}: low section ID {low} is greater than high section ID {high}")]
+    ReversedRange { low: u32, high: u32, line: usize },
+    #[error("line {line}, column {col}: expected a range of the form low-high, got {range:?}")]
+    MalformedRange {
+        range: String,
+        line: usize,
+        col: usize,
+    },
+    #[error("line {line}: expected two ranges separated by a comma, got {pair:?}")]
+    MalformedPair { pair: String, line: usize },
+    #[error("line {line}: failed to read input: {kind}")]
+    Io { kind: io::ErrorKind, line: usize },
+}
+
+/// The closed range of section IDs assigned to an elf.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Assignment(u32, u32);
 
+/// How one assignment relates to another, from the first one's point of view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Relationship {
+    /// No sections in common, with at least one section between them.
+    Disjoint,
+    /// No sections in common, but one ends right before the other starts.
+    Touching,
+    /// Some sections in common, and each has sections the other doesn't.
+    Partial,
+    /// Every section of the other, and more.
+    Contains,
+    /// Every section is also in the other, which has more.
+    Contained,
+    Equal,
+}
+
+impl Relationship {
+    pub const ALL: [Self; 6] = [
+        Self::Disjoint,
+        Self::Touching,
+        Self::Partial,
+        Self::Contains,
+        Self::Contained,
+        Self::Equal,
+    ];
+}
+
+impl Display for Relationship {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Disjoint => "disjoint",
+            Self::Touching => "touching",
+            Self::Partial => "partial",
+            Self::Contains => "contains",
+            Self::Contained => "contained",
+            Self::Equal => "equal",
+        };
+        write!(f, "{name}")
+    }
+}
+
 impl Assignment {
+    pub fn relationship(&self, other: &Assignment) -> Relationship {
+        use std::cmp::Ordering::{Equal, Greater, Less};
+
+        if self.1 < other.0 || other.1 < self.0 {
+            let (first, second) = if self.1 < other.0 {
+                (self, other)
+            } else {
+                (other, self)
+            };
+            return if second.0 - first.1 == 1 {
+                Relationship::Touching
+            } else {
+                Relationship::Disjoint
+            };
+        }
+        match (self.0.cmp(&other.0), self.1.cmp(&other.1)) {
+            (Equal, Equal) => Relationship::Equal,
+            (Less | Equal, Greater | Equal) => Relationship::Contains,
+            (Greater | Equal, Less | Equal) => Relationship::Contained,
+            _ => Relationship::Partial,
+        }
+    }
+
     pub fn fully_overlaps(&self, other: &Assignment) -> bool {
-        (self.0 <= other.0 && self.1 >= other.1) || (other.0 <= self.0 && other.1 >= self.1)
+        matches!(
+            self.relationship(other),
+            Relationship::Contains | Relationship::Contained | Relationship::Equal
+        )
     }
 
+    /// Whether the assignments share at least one section. Assignments that only touch don't.
     pub fn overlaps(&self, other: &Assignment) -> bool {
-        self.0 <= other.1 && other.0 <= self.1
+        !matches!(
+            self.relationship(other),
+            Relationship::Disjoint | Relationship::Touching
+        )
+    }
+}
+
+impl Display for Assignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.0, self.1)
+    }
+}
+
+impl Assignment {
+    /// Parses the low and high section IDs of a range which starts at `col` of `line`.
+    fn parse(low: &str, high: &str, line: usize, col: usize) -> Result<Self, Error> {
+        let parse_id = |token: &str, col| {
+            token.parse().map_err(|_| Error::InvalidSectionId {
+                token: token.to_string(),
+                line,
+                col,
+            })
+        };
+        let low_id = parse_id(low, col)?;
+        let high_id = parse_id(high, col + low.chars().count() + 1)?;
+        Self::try_from((low_id, high_id)).map_err(|_| Error::ReversedRange {
+            low: low_id,
+            high: high_id,
+            line,
+        })
+    }
+
+    fn parse_range(range: &str, line: usize, col: usize) -> Result<Self, Error> {
+        let (low, high) = range.split_once('-').ok_or_else(|| Error::MalformedRange {
+            range: range.to_string(),
+            line,
+            col,
+        })?;
+        Self::parse(low, high, line, col)
     }
 }
 
 impl TryFrom<(&str, &str)> for Assignment {
     type Error = Error;
 
-    fn try_from(value: (&str, &str)) -> Result<Self, Self::Error> {
-        let low = value.0.parse().context("parsing low str as u32")?;
-        let high = value.1.parse().context("parsing high str as u32")?;
-        if low > high {
-            return Err(Error::msg("low value is greater than high"));
+    fn try_from((low, high): (&str, &str)) -> Result<Self, Self::Error> {
+        let parse_id = |token: &str| -> Result<u32, Error> {
+            token.parse().map_err(|_| Error::NotASectionId {
+                token: token.to_string(),
+            })
+        };
+        Self::try_from((parse_id(low)?, parse_id(high)?))
+    }
+}
+
+/// Parses any number of comma-separated ranges.
+fn parse_line(ranges: &str, line: usize) -> Result<Vec<Assignment>, Error> {
+    let mut col = 1;
+    ranges
+        .split(',')
+        .map(|range| {
+            let assignment = Assignment::parse_range(range, line, col);
+            col += range.chars().count() + 1;
+            assignment
+        })
+        .collect()
+}
+
+fn parse_pair(pair: &str, line: usize) -> Result<(Assignment, Assignment), Error> {
+    let malformed = || Error::MalformedPair {
+        pair: pair.to_string(),
+        line,
+    };
+    if pair.matches(',').count() != 1 {
+        return Err(malformed());
+    }
+    match parse_line(pair, line)?[..] {
+        [a, b] => Ok((a, b)),
+        _ => Err(malformed()),
+    }
+}
+
+fn lines_from_reader<T>(
+    reader: impl BufRead,
+    parse: impl Fn(&str, usize) -> Result<T, Error>,
+) -> impl Iterator<Item = Result<T, Error>> {
+    (1..).zip(reader.lines()).map(move |(line, ranges)| {
+        let ranges = ranges.map_err(|e| Error::Io {
+            kind: e.kind(),
+            line,
+        })?;
+        parse(&ranges, line)
+    })
+}
+
+/// Parses one pair of assignments per line, reporting errors against their position in `input`.
+pub fn parse_pairs(input: &str) -> Result<Vec<(Assignment, Assignment)>, Error> {
+    pairs_from_reader(input.as_bytes()).collect()
+}
+
+/// Reads one pair of assignments per line, so only the current line is held in memory.
+pub fn pairs_from_reader(
+    reader: impl BufRead,
+) -> impl Iterator<Item = Result<(Assignment, Assignment), Error>> {
+    lines_from_reader(reader, parse_pair)
+}
+
+/// Parses any number of comma-separated assignments per line.
+pub fn parse_assignments(input: &str) -> Result<Vec<Vec<Assignment>>, Error> {
+    assignments_from_reader(input.as_bytes()).collect()
+}
+
+/// Reads any number of comma-separated assignments per line, one line at a time.
+pub fn assignments_from_reader(
+    reader: impl BufRead,
+) -> impl Iterator<Item = Result<Vec<Assignment>, Error>> {
+    lines_from_reader(reader, parse_line)
+}
+
+/// Number of pairs in which one assignment fully contains the other.
+pub fn count_fully_overlapping(
+    pairs: impl IntoIterator<Item = Result<(Assignment, Assignment), Error>>,
+) -> Result<usize, Error> {
+    let mut overlaps = 0;
+    for pair in pairs {
+        let (a, b) = pair?;
+        if a.fully_overlaps(&b) {
+            overlaps += 1;
+        }
+    }
+    Ok(overlaps)
+}
+
+/// Number of pairs whose assignments overlap at all.
+pub fn count_overlapping(
+    pairs: impl IntoIterator<Item = Result<(Assignment, Assignment), Error>>,
+) -> Result<usize, Error> {
+    let mut overlaps = 0;
+    for pair in pairs {
+        let (a, b) = pair?;
+        if a.overlaps(&b) {
+            overlaps += 1;
         }
-        Ok(Self(low, high))
+    }
+    Ok(overlaps)
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn part1(input: &str) -> anyhow::Result<usize> {
+        Ok(count_fully_overlapping(pairs_from_reader(
+            input.as_bytes(),
+        ))?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<usize> {
+        Ok(count_overlapping(pairs_from_reader(input.as_bytes()))?)
     }
 }
 
@@ -60,7 +313,115 @@ mod tests {
         assert!(assignment!("1", "1").overlaps(&assignment!("1", "2")));
         assert!(assignment!("1", "2").overlaps(&assignment!("1", "1")));
 
-        assert!(assignment!("1", "2").overlaps(&assignment!("3", "4")));
-        assert!(assignment!("3", "4").overlaps(&assignment!("1", "2")));
+        assert!(!assignment!("1", "2").overlaps(&assignment!("3", "4")));
+        assert!(!assignment!("3", "4").overlaps(&assignment!("1", "2")));
+        assert!(!assignment!("1", "2").overlaps(&assignment!("5", "6")));
+    }
+
+    #[test]
+    fn relationship() {
+        use Relationship::*;
+
+        assert_eq!(
+            Disjoint,
+            assignment!("1", "2").relationship(&assignment!("4", "5"))
+        );
+        assert_eq!(
+            Touching,
+            assignment!("1", "2").relationship(&assignment!("3", "4"))
+        );
+        assert_eq!(
+            Touching,
+            assignment!("3", "4").relationship(&assignment!("1", "2"))
+        );
+        assert_eq!(
+            Partial,
+            assignment!("1", "2").relationship(&assignment!("2", "3"))
+        );
+        assert_eq!(
+            Partial,
+            assignment!("2", "4").relationship(&assignment!("1", "3"))
+        );
+        assert_eq!(
+            Contains,
+            assignment!("1", "3").relationship(&assignment!("2", "3"))
+        );
+        assert_eq!(
+            Contained,
+            assignment!("2", "3").relationship(&assignment!("1", "3"))
+        );
+        assert_eq!(
+            Contained,
+            assignment!("5", "5").relationship(&assignment!("4", "6"))
+        );
+        assert_eq!(
+            Equal,
+            assignment!("1", "3").relationship(&assignment!("1", "3"))
+        );
+    }
+
+    #[test]
+    fn assignments() {
+        let lines = parse_assignments("1-2,3-4,5-6\n7-8\n").unwrap();
+        assert_eq!(vec![3, 1], lines.iter().map(Vec::len).collect::<Vec<_>>());
+        assert_eq!(assignment!("5", "6"), lines[0][2]);
+        assert_eq!(
+            Some(Error::InvalidSectionId {
+                token: "x".into(),
+                line: 2,
+                col: 9
+            }),
+            parse_assignments("1-2\n1-2,3-4,x-6\n").err()
+        );
+        assert_eq!(
+            Some(Error::MalformedPair {
+                pair: "1-2,3-4,5-6".into(),
+                line: 1
+            }),
+            parse_pairs("1-2,3-4,5-6\n").err()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Some(Error::InvalidSectionId {
+                token: "x".into(),
+                line: 2,
+                col: 7
+            }),
+            parse_pairs("1-2,3-4\n10-20,x-40\n").err()
+        );
+        assert_eq!(
+            Some(Error::ReversedRange {
+                low: 4,
+                high: 3,
+                line: 1
+            }),
+            parse_pairs("1-2,4-3\n").err()
+        );
+        assert_eq!(
+            Some(Error::MalformedRange {
+                range: "34".into(),
+                line: 1,
+                col: 5
+            }),
+            parse_pairs("1-2,34\n").err()
+        );
+        assert_eq!(
+            Some(Error::MalformedPair {
+                pair: "1-2".into(),
+                line: 1
+            }),
+            parse_pairs("1-2\n").err()
+        );
+        assert_eq!(
+            Some(Error::NotASectionId { token: "x".into() }),
+            Assignment::try_from(("1", "x")).err()
+        );
+        assert_eq!(
+            Some(Error::ReversedBounds { low: 4, high: 3 }),
+            Assignment::try_from(("4", "3")).err()
+        );
     }
 }
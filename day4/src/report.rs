@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use crate::{Assignment, Error, Relationship};
+
+/// How the two assignments on each line relate, with counts for the whole file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// Each pair and how the first assignment relates to the second, in line order.
+    pub pairs: Vec<(Assignment, Assignment, Relationship)>,
+}
+
+impl CoverageReport {
+    pub fn new(
+        pairs: impl IntoIterator<Item = Result<(Assignment, Assignment), Error>>,
+    ) -> Result<Self, Error> {
+        let pairs = pairs
+            .into_iter()
+            .map(|pair| {
+                let (a, b) = pair?;
+                Ok((a, b, a.relationship(&b)))
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Self { pairs })
+    }
+
+    pub fn count(&self, relationship: Relationship) -> usize {
+        self.pairs
+            .iter()
+            .filter(|(_, _, kind)| *kind == relationship)
+            .count()
+    }
+
+    /// Number of pairs of each kind, in the order of [`Relationship::ALL`].
+    pub fn counts(&self) -> [(Relationship, usize); 6] {
+        Relationship::ALL.map(|relationship| (relationship, self.count(relationship)))
+    }
+}
+
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (line, (a, b, relationship)) in (1..).zip(&self.pairs) {
+            writeln!(f, "line {line}: {a},{b} {relationship}")?;
+        }
+        let counts: Vec<String> = self
+            .counts()
+            .iter()
+            .map(|(relationship, count)| format!("{relationship}: {count}"))
+            .collect();
+        write!(f, "{}", counts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pairs_from_reader;
+
+    const EXAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+
+    #[test]
+    fn example() {
+        let report = CoverageReport::new(pairs_from_reader(EXAMPLE.as_bytes())).unwrap();
+        assert_eq!(
+            [
+                (Relationship::Disjoint, 1),
+                (Relationship::Touching, 1),
+                (Relationship::Partial, 2),
+                (Relationship::Contains, 1),
+                (Relationship::Contained, 1),
+                (Relationship::Equal, 0),
+            ],
+            report.counts()
+        );
+        assert_eq!(
+            "line 1: 2-4,6-8 disjoint\n\
+             line 2: 2-3,4-5 touching\n\
+             line 3: 5-7,7-9 partial\n\
+             line 4: 2-8,3-7 contains\n\
+             line 5: 6-6,4-6 contained\n\
+             line 6: 2-6,4-8 partial\n\
+             disjoint: 1, touching: 1, partial: 2, contains: 1, contained: 1, equal: 0",
+            report.to_string()
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::{env, fs};
+
+use anyhow::{Context, Error, Result};
+use day5::{
+    crane::parse_crane,
+    planner::{plan, Target},
+    Stacks,
+};
+
+const USAGE: &str = "\
+Usage:
+    day5-plan [--crane 9000|9001|limited:N|alternating:N] (--top CRATES | --target FILE)
+
+Reads a drawing of the starting stacks, or a whole puzzle input whose procedure is ignored, and
+prints puzzle input with a shortest procedure that leaves CRATES on top of the stacks, or that
+rearranges them into the drawing in FILE, using the given crane (default 9000).";
+
+fn main() -> Result<()> {
+    let mut crane = "9000".to_string();
+    let mut target = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--crane" => crane = args.next().context(USAGE)?,
+            "--top" => target = Some(Target::TopCrates(args.next().context(USAGE)?)),
+            "--target" => {
+                let path = args.next().context(USAGE)?;
+                let drawing =
+                    fs::read_to_string(&path).with_context(|| format!("could not read {path}"))?;
+                target = Some(Target::Stacks(Stacks::parse_drawing(drawing.trim_end())?));
+            }
+            _ => return Err(Error::msg(USAGE)),
+        }
+    }
+    let target = target.context(USAGE)?;
+    let crane = parse_crane(&crane)?;
+
+    let buffer = common::read_stdin()?;
+    let drawing = buffer
+        .split_once("\n\n")
+        .map_or(buffer.as_str(), |(drawing, _)| drawing);
+    let start = Stacks::parse_drawing(drawing.trim_end_matches('\n'))?;
+    print!("{}", plan(&start, &target, crane.as_ref())?);
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use crate::Error;
+
+/// A single trip of a crane: the number of crates it picks up off the top of a stack, and whether
+/// they come down upside down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lift {
+    pub crates: usize,
+    pub reversed: bool,
+}
+
+/// A crane model, which decides how a move of several crates is split into lifts.
+pub trait Crane {
+    fn lifts(&self, count: usize) -> Vec<Lift>;
+}
+
+/// Moves one crate at a time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn lifts(&self, count: usize) -> Vec<Lift> {
+        vec![
+            Lift {
+                crates: 1,
+                reversed: false
+            };
+            count
+        ]
+    }
+}
+
+/// Moves all the crates at once, keeping their order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn lifts(&self, count: usize) -> Vec<Lift> {
+        vec![Lift {
+            crates: count,
+            reversed: false,
+        }]
+    }
+}
+
+/// Moves at most `capacity` crates per lift, keeping their order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LimitedCrane {
+    capacity: usize,
+}
+
+impl LimitedCrane {
+    pub fn new(capacity: usize) -> Result<Self, Error> {
+        if capacity == 0 {
+            return Err(Error::ZeroCapacity);
+        }
+        Ok(Self { capacity })
+    }
+}
+
+impl Crane for LimitedCrane {
+    fn lifts(&self, count: usize) -> Vec<Lift> {
+        split(count, self.capacity)
+            .map(|crates| Lift {
+                crates,
+                reversed: false,
+            })
+            .collect()
+    }
+}
+
+/// Moves at most `capacity` crates per lift, and turns the second, fourth and so on lift of each
+/// move upside down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AlternatingCrane {
+    capacity: usize,
+}
+
+impl AlternatingCrane {
+    pub fn new(capacity: usize) -> Result<Self, Error> {
+        if capacity == 0 {
+            return Err(Error::ZeroCapacity);
+        }
+        Ok(Self { capacity })
+    }
+}
+
+impl Crane for AlternatingCrane {
+    fn lifts(&self, count: usize) -> Vec<Lift> {
+        split(count, self.capacity)
+            .enumerate()
+            .map(|(index, crates)| Lift {
+                crates,
+                reversed: index % 2 == 1,
+            })
+            .collect()
+    }
+}
+
+/// Sizes of the lifts needed to move `count` crates at most `capacity` at a time.
+fn split(count: usize, capacity: usize) -> impl Iterator<Item = usize> {
+    (0..count.div_ceil(capacity)).map(move |lift| capacity.min(count - lift * capacity))
+}
+
+/// Picks a crane by name: `9000`, `9001`, `limited:N` or `alternating:N`.
+pub fn parse_crane(name: &str) -> Result<Box<dyn Crane>, Error> {
+    let capacity = |capacity: &str| {
+        capacity.parse().map_err(|_| Error::UnknownCrane {
+            name: name.to_string(),
+        })
+    };
+    Ok(match name.split_once(':') {
+        None if name == "9000" => Box::new(CrateMover9000),
+        None if name == "9001" => Box::new(CrateMover9001),
+        Some(("limited", n)) => Box::new(LimitedCrane::new(capacity(n)?)?),
+        Some(("alternating", n)) => Box::new(AlternatingCrane::new(capacity(n)?)?),
+        _ => {
+            return Err(Error::UnknownCrane {
+                name: name.to_string(),
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stacks;
+
+    fn rearrange(crane: &dyn Crane) -> Stacks {
+        let drawing = "[A]\n[B]\n[C]\n[D]\n[E]\n 1   2 ";
+        let mut stacks = Stacks::parse_drawing(drawing).unwrap();
+        stacks
+            .apply(&"move 5 from 1 to 2".try_into().unwrap(), crane)
+            .unwrap();
+        stacks
+    }
+
+    fn second_stack(stacks: &Stacks) -> String {
+        stacks.to_string().lines().map(|line| &line[4..7]).collect()
+    }
+
+    #[test]
+    fn cranes() {
+        assert_eq!(
+            "[E][D][C][B][A] 2 ",
+            second_stack(&rearrange(&CrateMover9000))
+        );
+        assert_eq!(
+            "[A][B][C][D][E] 2 ",
+            second_stack(&rearrange(&CrateMover9001))
+        );
+        assert_eq!(
+            "[D][E][A][B][C] 2 ",
+            second_stack(&rearrange(&LimitedCrane::new(3).unwrap()))
+        );
+        assert_eq!(
+            "[E][D][C][A][B] 2 ",
+            second_stack(&rearrange(&AlternatingCrane::new(2).unwrap()))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Some(Error::ZeroCapacity), LimitedCrane::new(0).err());
+        assert!(parse_crane("alternating:2").is_ok());
+        assert_eq!(
+            Some(Error::UnknownCrane {
+                name: "limited:x".into()
+            }),
+            parse_crane("limited:x").err()
+        );
+        assert_eq!(
+            Some(Error::UnknownCrane {
+                name: "9002".into()
+            }),
+            parse_crane("9002").err()
+        );
+    }
+}
@@ -1,66 +1,645 @@
-use anyhow::{Context, Result};
-use std::collections::VecDeque;
+use common::Solver;
+use crane::{Crane, CrateMover9000, CrateMover9001, Lift};
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+use thiserror::Error;
 
-#[derive(Debug)]
-pub struct Stacks(Vec<VecDeque<char>>);
+pub mod crane;
+pub mod planner;
+pub mod recorder;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("stack {stack} does not exist")]
+    NoSuchStack { stack: usize },
+    #[error("cannot move {requested} crate(s) from stack {stack}, which holds {available}")]
+    NotEnoughCrates {
+        stack: usize,
+        requested: usize,
+        available: usize,
+    },
+    #[error("expected a move of the form `move N from A to B`, got {instruction:?}")]
+    NotAMove { instruction: String },
+    #[error("{token:?} is not a valid number")]
+    NotANumber { token: String },
+    #[error("line {line}: expected a move of the form `move N from A to B`, got {instruction:?}")]
+    MalformedMove { instruction: String, line: usize },
+    #[error("line {line}: {token:?} is not a valid number")]
+    InvalidNumber { token: String, line: usize },
+    #[error("expected the drawing and procedure to be separated by a blank line")]
+    MissingProcedure,
+    #[error("expected the drawing to end with a line numbering the stacks")]
+    MissingFooter,
+    #[error("line {line}, column {col}: {token:?} is not a stack number")]
+    InvalidFooter {
+        token: String,
+        line: usize,
+        col: usize,
+    },
+    #[error("line {line}, column {col}: expected stack {expected}, got {got}")]
+    MisnumberedStack {
+        expected: usize,
+        got: usize,
+        line: usize,
+        col: usize,
+    },
+    #[error("line {line}, column {col}: expected a crate of the form [label], got {token:?}")]
+    MalformedCrate {
+        token: String,
+        line: usize,
+        col: usize,
+    },
+    #[error("line {line}, column {col}: crate {label} is not above exactly one free stack number")]
+    MisalignedCrate {
+        label: String,
+        line: usize,
+        col: usize,
+    },
+    #[error("a crane must be able to lift at least one crate")]
+    ZeroCapacity,
+    #[error("{name:?} is not a crane, expected 9000, 9001, limited:N or alternating:N")]
+    UnknownCrane { name: String },
+    #[error("line {line}: crate {label} in stack {stack} has nothing underneath it")]
+    FloatingCrate {
+        label: String,
+        stack: usize,
+        line: usize,
+    },
+    #[error("the target cannot be reached from the starting stacks")]
+    Unreachable,
+    #[error("no plan found after searching {states} arrangements of the stacks")]
+    SearchLimit { states: usize },
+}
+
+/// A `move N from A to B` step of the rearrangement procedure. Stacks are numbered from 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub count: usize,
+    pub source: usize,
+    pub destination: usize,
+}
+
+impl Move {
+    fn parse(instruction: &str, line: usize) -> Result<Self, Error> {
+        Self::try_from(instruction).map_err(|e| match e {
+            Error::NotAMove { instruction } => Error::MalformedMove { instruction, line },
+            Error::NotANumber { token } => Error::InvalidNumber { token, line },
+            e => e,
+        })
+    }
+}
+
+impl TryFrom<&str> for Move {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let parse_number = |token: &str| {
+            token.parse().map_err(|_| Error::NotANumber {
+                token: token.to_string(),
+            })
+        };
+
+        let tokens: Vec<&str> = value.split_whitespace().collect();
+        match tokens[..] {
+            ["move", count, "from", source, "to", destination] => Ok(Self {
+                count: parse_number(count)?,
+                source: parse_number(source)?,
+                destination: parse_number(destination)?,
+            }),
+            _ => Err(Error::NotAMove {
+                instruction: value.to_string(),
+            }),
+        }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count, self.source, self.destination
+        )
+    }
+}
+
+/// A move as the crane carried it out, so it can be undone and redone.
+#[derive(Clone, Debug)]
+struct LoggedMove {
+    step: Move,
+    lifts: Vec<Lift>,
+}
+
+/// Stacks of crates, each labelled with one or more characters, along with the moves that can be
+/// undone and redone. Stacks compare equal when they hold the same crates, however they got
+/// there.
+#[derive(Clone, Debug)]
+pub struct Stacks {
+    /// The crates in each stack, top first.
+    crates: Vec<VecDeque<String>>,
+    /// Moves carried out so far, most recent last.
+    undo: Vec<LoggedMove>,
+    /// Moves undone since the last new move, most recently undone last.
+    redo: Vec<LoggedMove>,
+}
+
+impl PartialEq for Stacks {
+    fn eq(&self, other: &Self) -> bool {
+        self.crates == other.crates
+    }
+}
+
+impl Eq for Stacks {}
+
+impl Hash for Stacks {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.crates.hash(state);
+    }
+}
+
+impl Default for Stacks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Stacks {
     pub fn new() -> Self {
-        Self(Vec::with_capacity(9))
+        Self {
+            crates: Vec::with_capacity(9),
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// A copy of the crates without the undo and redo history.
+    pub fn snapshot(&self) -> Self {
+        Self {
+            crates: self.crates.clone(),
+            ..Self::new()
+        }
     }
 
-    pub fn add_crate(&mut self, column: usize, supply: char) {
-        while self.0.len() <= column {
-            self.0.push(VecDeque::new());
+    /// Puts a crate underneath the ones already in the 0-based `column`.
+    pub fn add_crate(&mut self, column: usize, label: impl Into<String>) {
+        while self.crates.len() <= column {
+            self.crates.push(VecDeque::new());
         }
-        self.0
+        self.crates
             .get_mut(column)
             .expect("ensured to have enough columns")
-            .push_back(supply);
+            .push_back(label.into());
     }
 
-    pub fn move_crate(&mut self, source: usize, destination: usize) -> Result<()> {
+    pub fn move_crate(&mut self, source: usize, destination: usize) -> Result<(), Error> {
         self.move_crates(source, destination, 1)
     }
 
-    pub fn move_crates(&mut self, source: usize, destination: usize, number: usize) -> Result<()> {
+    pub fn move_crates(
+        &mut self,
+        source: usize,
+        destination: usize,
+        number: usize,
+    ) -> Result<(), Error> {
+        let step = Move {
+            count: number,
+            source,
+            destination,
+        };
+        self.apply(&step, &CrateMover9001)
+    }
+
+    /// Carries out a move with the given crane. Nothing is moved if the move is invalid.
+    pub fn apply(&mut self, step: &Move, crane: &dyn Crane) -> Result<(), Error> {
         // Source and destination are 1-based
-        let source = source - 1;
-        let destination = destination - 1;
-        let mut popped = VecDeque::with_capacity(number);
-
-        let source = self
-            .0
-            .get_mut(source)
-            .context("source stack greater than the number of stacks")?;
-        for _ in 0..number {
-            popped.push_back(
-                source
-                    .pop_front()
-                    .context("attempted to pop from empty stack")?,
-            );
-        }
+        let stack_index = |stack: usize| {
+            stack
+                .checked_sub(1)
+                .filter(|index| *index < self.crates.len())
+                .ok_or(Error::NoSuchStack { stack })
+        };
+        let source_index = stack_index(step.source)?;
+        let destination_index = stack_index(step.destination)?;
 
-        let destination = self
-            .0
-            .get_mut(destination)
-            .context("destination stack greater than the number of stacks")?;
-        for _ in 0..number {
-            destination.push_front(
-                popped
-                    .pop_back()
-                    .expect("guaranteed to have enough entries"),
-            );
+        let available = self.crates[source_index].len();
+        if available < step.count {
+            return Err(Error::NotEnoughCrates {
+                stack: step.source,
+                requested: step.count,
+                available,
+            });
         }
 
+        let lifts = crane.lifts(step.count);
+        self.lift(source_index, destination_index, &lifts);
+        self.undo.push(LoggedMove { step: *step, lifts });
+        self.redo.clear();
         Ok(())
     }
 
+    fn lift<'a>(
+        &mut self,
+        source_index: usize,
+        destination_index: usize,
+        lifts: impl IntoIterator<Item = &'a Lift>,
+    ) {
+        for lift in lifts {
+            let mut lifted: VecDeque<_> = self.crates[source_index].drain(..lift.crates).collect();
+            if lift.reversed {
+                lifted.make_contiguous().reverse();
+            }
+            let destination = &mut self.crates[destination_index];
+            while let Some(label) = lifted.pop_back() {
+                destination.push_front(label);
+            }
+        }
+    }
+
+    /// Takes back the most recent move by repeating its lifts backwards, returning the move.
+    pub fn undo(&mut self) -> Option<Move> {
+        let logged = self.undo.pop()?;
+        let step = logged.step;
+        self.lift(
+            step.destination - 1,
+            step.source - 1,
+            logged.lifts.iter().rev(),
+        );
+        self.redo.push(logged);
+        Some(step)
+    }
+
+    /// Carries out the most recently undone move again, returning the move.
+    pub fn redo(&mut self) -> Option<Move> {
+        let logged = self.redo.pop()?;
+        let step = logged.step;
+        self.lift(step.source - 1, step.destination - 1, &logged.lifts);
+        self.undo.push(logged);
+        Some(step)
+    }
+
+    /// Parses the starting drawing of crates. The last line numbers the stacks from 1, and each
+    /// crate above it is a `[label]` sitting over the number of its stack.
+    pub fn parse_drawing(drawing: &str) -> Result<Self, Error> {
+        let lines: Vec<&str> = drawing.lines().collect();
+        let (footer, rows) = lines.split_last().ok_or(Error::MissingFooter)?;
+        let footer_line = lines.len();
+        let columns = tokens(footer)
+            .into_iter()
+            .zip(1..)
+            .map(|((span, token), expected)| {
+                let col = span.start + 1;
+                let got = token.parse().map_err(|_| Error::InvalidFooter {
+                    token: token.to_string(),
+                    line: footer_line,
+                    col,
+                })?;
+                if got != expected {
+                    return Err(Error::MisnumberedStack {
+                        expected,
+                        got,
+                        line: footer_line,
+                        col,
+                    });
+                }
+                Ok(span)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if columns.is_empty() {
+            return Err(Error::MissingFooter);
+        }
+
+        let mut stacks = Stacks {
+            crates: vec![VecDeque::new(); columns.len()],
+            ..Stacks::new()
+        };
+        for (line, row) in (1..).zip(rows) {
+            let mut filled = vec![false; columns.len()];
+            for (span, token) in tokens(row) {
+                let col = span.start + 1;
+                let label = token
+                    .strip_prefix('[')
+                    .and_then(|token| token.strip_suffix(']'))
+                    .filter(|label| !label.is_empty() && !label.contains(['[', ']']))
+                    .ok_or_else(|| Error::MalformedCrate {
+                        token: token.to_string(),
+                        line,
+                        col,
+                    })?;
+                let mut below = (0..columns.len()).filter(|&stack| {
+                    columns[stack].start < span.end && span.start < columns[stack].end
+                });
+                let stack = match (below.next(), below.next()) {
+                    (Some(stack), None) if !filled[stack] => stack,
+                    _ => {
+                        return Err(Error::MisalignedCrate {
+                            label: label.to_string(),
+                            line,
+                            col,
+                        })
+                    }
+                };
+                filled[stack] = true;
+                stacks.crates[stack].push_back(label.to_string());
+            }
+
+            // Once a stack has started, every row below needs a crate in it.
+            for (stack, crates) in stacks.crates.iter().enumerate() {
+                if let (false, Some(label)) = (filled[stack], crates.back()) {
+                    return Err(Error::FloatingCrate {
+                        label: label.clone(),
+                        stack: stack + 1,
+                        line: line - 1,
+                    });
+                }
+            }
+        }
+        Ok(stacks)
+    }
+
+    /// Labels of the top crate of each stack, with a space for each empty stack.
     pub fn top_crates(&self) -> String {
         let mut top_crates = String::new();
-        for stack in &self.0 {
-            top_crates.push(*stack.get(0).unwrap_or(&' '));
+        for stack in &self.crates {
+            top_crates.push_str(stack.front().map_or(" ", String::as_str));
         }
         top_crates
     }
 }
+
+/// Splits a line of the drawing into whitespace-separated tokens and the 0-based character
+/// columns each one spans.
+fn tokens(line: &str) -> Vec<(Range<usize>, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut col = 0;
+    for (offset, ch) in line.char_indices() {
+        match (ch.is_whitespace(), start) {
+            (false, None) => start = Some((col, offset)),
+            (true, Some((start_col, start_offset))) => {
+                tokens.push((start_col..col, &line[start_offset..offset]));
+                start = None;
+            }
+            _ => (),
+        }
+        col += 1;
+    }
+    if let Some((start_col, start_offset)) = start {
+        tokens.push((start_col..col, &line[start_offset..]));
+    }
+    tokens
+}
+
+/// Draws the stacks the way the puzzle input does, widening every column to fit the longest
+/// label or stack number.
+impl Display for Stacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .crates
+            .iter()
+            .flatten()
+            .map(|label| label.chars().count() + 2)
+            .chain([self.crates.len().to_string().len()])
+            .max()
+            .unwrap_or(1);
+        let height = self.crates.iter().map(VecDeque::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row: Vec<String> = self
+                .crates
+                .iter()
+                .map(|stack| match stack.len().checked_sub(level + 1) {
+                    Some(index) => format!("{:^width$}", format!("[{}]", stack[index])),
+                    None => " ".repeat(width),
+                })
+                .collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+        let footer: Vec<String> = (1..=self.crates.len())
+            .map(|stack| format!("{stack:^width$}"))
+            .collect();
+        write!(f, "{}", footer.join(" "))
+    }
+}
+
+/// Splits the puzzle input into the starting stacks and the rearrangement procedure.
+pub fn parse_input(input: &str) -> Result<(Stacks, Vec<Move>), Error> {
+    let (drawing, procedure) = input.split_once("\n\n").ok_or(Error::MissingProcedure)?;
+    let stacks = Stacks::parse_drawing(drawing)?;
+    // The procedure starts after the drawing and the blank line separating them
+    let first_move_line = drawing.lines().count() + 2;
+    let moves = procedure
+        .lines()
+        .enumerate()
+        .map(|(index, instruction)| Move::parse(instruction, first_move_line + index))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((stacks, moves))
+}
+
+/// Top crates after carrying out the whole procedure with the given crane.
+pub fn rearrange(input: &str, crane: &dyn Crane) -> Result<String, Error> {
+    let (mut stacks, moves) = parse_input(input)?;
+    for step in &moves {
+        stacks.apply(step, crane)?;
+    }
+    Ok(stacks.top_crates())
+}
+
+/// Top crates after rearranging with the CrateMover 9000, which moves one crate at a time.
+pub fn solve_part1(input: &str) -> Result<String, Error> {
+    rearrange(input, &CrateMover9000)
+}
+
+/// Top crates after rearranging with the CrateMover 9001, which moves several crates at once.
+pub fn solve_part2(input: &str) -> Result<String, Error> {
+    rearrange(input, &CrateMover9001)
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Part1 = String;
+    type Part2 = String;
+
+    fn part1(input: &str) -> anyhow::Result<String> {
+        Ok(solve_part1(input)?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<String> {
+        Ok(solve_part2(input)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+
+    #[test]
+    fn parse_move() {
+        let step = Move::try_from("move 3 from 1 to 3").unwrap();
+        assert_eq!(
+            Move {
+                count: 3,
+                source: 1,
+                destination: 3
+            },
+            step
+        );
+        assert_eq!("move 3 from 1 to 3", step.to_string());
+        assert_eq!(
+            Some(Error::NotAMove {
+                instruction: "move 3 from 1".into()
+            }),
+            Move::try_from("move 3 from 1").err()
+        );
+        assert_eq!(
+            Some(Error::NotANumber { token: "x".into() }),
+            Move::try_from("move x from 1 to 3").err()
+        );
+        assert_eq!(
+            Some(Error::InvalidNumber {
+                token: "x".into(),
+                line: 4
+            }),
+            Move::parse("move x from 1 to 3", 4).err()
+        );
+    }
+
+    #[test]
+    fn move_errors() {
+        let (mut stacks, _) = parse_input(EXAMPLE).unwrap();
+        assert_eq!(
+            Err(Error::NoSuchStack { stack: 4 }),
+            stacks.move_crate(1, 4)
+        );
+        assert_eq!(
+            Err(Error::NoSuchStack { stack: 0 }),
+            stacks.move_crate(0, 1)
+        );
+        assert_eq!(
+            Err(Error::NotEnoughCrates {
+                stack: 3,
+                requested: 2,
+                available: 1
+            }),
+            stacks.move_crates(3, 1, 2)
+        );
+        // Failed moves leave the stacks untouched
+        assert_eq!("NDP", stacks.top_crates());
+    }
+
+    #[test]
+    fn drawing() {
+        let drawing = EXAMPLE.split_once("\n\n").unwrap().0;
+        let stacks = Stacks::parse_drawing(drawing).unwrap();
+        assert_eq!("NDP", stacks.top_crates());
+        assert_eq!(drawing, stacks.to_string());
+
+        let mut stacks = Stacks::new();
+        for column in 0..11 {
+            stacks.add_crate(column, "A");
+        }
+        stacks.add_crate(3, "Long");
+        let drawing = stacks.to_string();
+        assert_eq!(
+            concat!(
+                "                      [A]                                                   \n",
+                " [A]    [A]    [A]   [Long]  [A]    [A]    [A]    [A]    [A]    [A]    [A]  \n",
+                "  1      2      3      4      5      6      7      8      9      10     11  ",
+            ),
+            drawing
+        );
+        assert_eq!(stacks, Stacks::parse_drawing(&drawing).unwrap());
+    }
+
+    #[test]
+    fn drawing_errors() {
+        assert_eq!(Some(Error::MissingFooter), Stacks::parse_drawing("").err());
+        assert_eq!(
+            Some(Error::MisnumberedStack {
+                expected: 2,
+                got: 3,
+                line: 2,
+                col: 6
+            }),
+            Stacks::parse_drawing("[A] [B]\n 1   3 ").err()
+        );
+        assert_eq!(
+            Some(Error::InvalidFooter {
+                token: "[B]".into(),
+                line: 1,
+                col: 5
+            }),
+            Stacks::parse_drawing(" 1  [B]").err()
+        );
+        assert_eq!(
+            Some(Error::MalformedCrate {
+                token: "[B".into(),
+                line: 1,
+                col: 5
+            }),
+            Stacks::parse_drawing("[A] [B\n 1   2 ").err()
+        );
+        assert_eq!(
+            Some(Error::MisalignedCrate {
+                label: "B".into(),
+                line: 1,
+                col: 3
+            }),
+            Stacks::parse_drawing("  [B]\n 1   2 ").err()
+        );
+        assert_eq!(
+            Some(Error::FloatingCrate {
+                label: "A".into(),
+                stack: 1,
+                line: 1
+            }),
+            Stacks::parse_drawing("[A]\n    [B]\n 1   2 ").err()
+        );
+    }
+
+    #[test]
+    fn undo_redo() {
+        let (mut stacks, moves) = parse_input(EXAMPLE).unwrap();
+        let start = stacks.clone();
+        for step in &moves {
+            stacks.apply(step, &CrateMover9000).unwrap();
+        }
+        let end = stacks.clone();
+
+        assert_eq!(Some(moves[3]), stacks.undo());
+        assert_eq!(Some(moves[2]), stacks.undo());
+        assert_eq!(Some(moves[2]), stacks.redo());
+        for _ in 0..3 {
+            stacks.undo();
+        }
+        assert_eq!(None, stacks.undo());
+        assert_eq!(start, stacks);
+        while stacks.redo().is_some() {}
+        assert_eq!(end, stacks);
+
+        stacks.undo();
+        stacks.move_crate(1, 2).unwrap();
+        assert_eq!(None, stacks.redo());
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!("CMZ", solve_part1(EXAMPLE).unwrap());
+        assert_eq!("MCD", solve_part2(EXAMPLE).unwrap());
+    }
+}
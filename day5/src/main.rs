@@ -0,0 +1,84 @@
+use std::{env, io, time::Duration};
+
+use anyhow::{Context, Error, Result};
+use day5::{crane::parse_crane, recorder::Recording};
+
+const USAGE: &str = "\
+Usage:
+    day5
+    day5 --crane 9000|9001|limited:N|alternating:N [--animate [--delay MS] | --step N]
+
+Without options, prints the top crates after rearranging with the CrateMover 9000 and with the
+CrateMover 9001. --crane prints them for the given crane instead. --animate plays the
+rearrangement in the terminal, waiting MS milliseconds between moves (default 500). --step prints
+the stacks after the first N moves.";
+
+fn main() -> Result<()> {
+    let mut crane = None;
+    let mut animate = false;
+    let mut delay = None;
+    let mut step = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--crane" => crane = Some(args.next().context(USAGE)?),
+            "--animate" => animate = true,
+            "--delay" => {
+                let ms = args.next().context(USAGE)?;
+                let ms = ms
+                    .parse()
+                    .with_context(|| format!("{ms} is not a valid MS"))?;
+                delay = Some(Duration::from_millis(ms));
+            }
+            "--step" => {
+                let n = args.next().context(USAGE)?;
+                step = Some(n.parse().with_context(|| format!("{n} is not a valid N"))?);
+            }
+            _ => return Err(Error::msg(USAGE)),
+        }
+    }
+
+    if (animate && step.is_some()) || (delay.is_some() && !animate) {
+        return Err(Error::msg(USAGE));
+    }
+
+    let buffer = common::read_stdin()?;
+    let Some(crane) = crane else {
+        if animate || step.is_some() {
+            return Err(Error::msg(USAGE));
+        }
+        println!(
+            "CrateMover 9000 top crates: {}",
+            day5::solve_part1(&buffer)?
+        );
+        println!(
+            "CrateMover 9001 top crates: {}",
+            day5::solve_part2(&buffer)?
+        );
+        return Ok(());
+    };
+    let crane = parse_crane(&crane)?;
+
+    if !animate && step.is_none() {
+        println!(
+            "Top crates in each stack: {}",
+            day5::rearrange(&buffer, crane.as_ref())?
+        );
+        return Ok(());
+    }
+    let (stacks, moves) = day5::parse_input(&buffer)?;
+    let recording = Recording::record(stacks, &moves, crane.as_ref())?;
+    match step {
+        Some(n) => {
+            let frame = recording
+                .frame(n)
+                .with_context(|| format!("there are only {} steps", recording.steps()))?;
+            println!("{frame}");
+        }
+        None => recording.animate(
+            &mut io::stdout().lock(),
+            delay.unwrap_or(Duration::from_millis(500)),
+        )?,
+    }
+    Ok(())
+}
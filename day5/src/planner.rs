@@ -0,0 +1,204 @@
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{crane::Crane, Error, Move, Stacks};
+
+/// Arrangements of the stacks to search before giving up.
+pub const MAX_STATES: usize = 1_000_000;
+
+/// What the stacks should look like once the procedure is done.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// The crate on top of each stack, as given by [`Stacks::top_crates`].
+    TopCrates(String),
+    /// Every crate in every stack.
+    Stacks(Stacks),
+}
+
+impl Target {
+    fn reached_by(&self, stacks: &Stacks) -> bool {
+        match self {
+            Target::TopCrates(top_crates) => stacks.top_crates() == *top_crates,
+            Target::Stacks(target) => stacks == target,
+        }
+    }
+}
+
+/// A starting drawing and a procedure that rearranges it, which displays as puzzle input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub start: Stacks,
+    pub moves: Vec<Move>,
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}\n", self.start)?;
+        for step in &self.moves {
+            writeln!(f, "{step}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds a shortest procedure that takes `start` to `target` with the given crane, searching at
+/// most [`MAX_STATES`] arrangements.
+pub fn plan(start: &Stacks, target: &Target, crane: &dyn Crane) -> Result<Plan, Error> {
+    search(start, target, crane, MAX_STATES)
+}
+
+/// Breadth-first search over arrangements of the stacks, so the first arrangement that reaches
+/// the target does so in the fewest moves.
+fn search(
+    start: &Stacks,
+    target: &Target,
+    crane: &dyn Crane,
+    max_states: usize,
+) -> Result<Plan, Error> {
+    if let Target::Stacks(target) = target {
+        if !same_crates(start, target) {
+            return Err(Error::Unreachable);
+        }
+    }
+
+    // Each arrangement found, with the one it was reached from and the move that did it.
+    let mut states: Vec<(Stacks, Option<(usize, Move)>)> = vec![(start.snapshot(), None)];
+    let mut seen = HashSet::from([start.snapshot()]);
+    let mut next = 0;
+    while let Some((stacks, _)) = states.get(next) {
+        if target.reached_by(stacks) {
+            return Ok(Plan {
+                start: start.snapshot(),
+                moves: moves_to(&states, next),
+            });
+        }
+        let stacks = stacks.snapshot();
+        for step in possible_moves(&stacks) {
+            let mut after = stacks.snapshot();
+            after
+                .apply(&step, crane)
+                .expect("only moves of crates that are there are tried");
+            after.undo.clear();
+            if seen.insert(after.snapshot()) {
+                if states.len() == max_states {
+                    return Err(Error::SearchLimit { states: max_states });
+                }
+                states.push((after, Some((next, step))));
+            }
+        }
+        next += 1;
+    }
+    Err(Error::Unreachable)
+}
+
+/// Whether both have the same number of stacks holding the same crates, in any order.
+fn same_crates(a: &Stacks, b: &Stacks) -> bool {
+    fn labels(stacks: &Stacks) -> Vec<&String> {
+        let mut labels: Vec<&String> = stacks.crates.iter().flatten().collect();
+        labels.sort_unstable();
+        labels
+    }
+    a.crates.len() == b.crates.len() && labels(a) == labels(b)
+}
+
+/// Every move of one or more crates from a stack to a different one.
+fn possible_moves(stacks: &Stacks) -> impl Iterator<Item = Move> + '_ {
+    let stack_count = stacks.crates.len();
+    (1..=stack_count).flat_map(move |source| {
+        (1..=stacks.crates[source - 1].len()).flat_map(move |count| {
+            (1..=stack_count)
+                .filter(move |&destination| destination != source)
+                .map(move |destination| Move {
+                    count,
+                    source,
+                    destination,
+                })
+        })
+    })
+}
+
+/// The moves that lead from the starting arrangement to `states[index]`.
+fn moves_to(states: &[(Stacks, Option<(usize, Move)>)], mut index: usize) -> Vec<Move> {
+    let mut moves = Vec::new();
+    while let Some((previous, step)) = states[index].1 {
+        moves.push(step);
+        index = previous;
+    }
+    moves.reverse();
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crane::{CrateMover9000, CrateMover9001},
+        parse_input, rearrange,
+    };
+
+    const DRAWING: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
+
+    fn start() -> Stacks {
+        Stacks::parse_drawing(DRAWING).unwrap()
+    }
+
+    #[test]
+    fn top_crates() {
+        let found = plan(&start(), &Target::TopCrates("CMZ".into()), &CrateMover9000).unwrap();
+        assert_eq!(2, found.moves.len());
+        assert_eq!(
+            "CMZ",
+            rearrange(&found.to_string(), &CrateMover9000).unwrap()
+        );
+
+        let found = plan(&start(), &Target::TopCrates("NDP".into()), &CrateMover9000).unwrap();
+        assert_eq!(format!("{DRAWING}\n\n"), found.to_string());
+    }
+
+    #[test]
+    fn full_target() {
+        let input = format!(
+            "{DRAWING}\n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n"
+        );
+        let (mut target, moves) = parse_input(&input).unwrap();
+        for step in &moves {
+            target.apply(step, &CrateMover9001).unwrap();
+        }
+        let plan = plan(&start(), &Target::Stacks(target.clone()), &CrateMover9001).unwrap();
+        assert!(plan.moves.len() <= moves.len());
+
+        let (mut stacks, moves) = parse_input(&plan.to_string()).unwrap();
+        for step in &moves {
+            stacks.apply(step, &CrateMover9001).unwrap();
+        }
+        assert_eq!(target, stacks);
+    }
+
+    #[test]
+    fn errors() {
+        let mut target = start();
+        target.add_crate(1, "X");
+        assert_eq!(
+            Some(Error::Unreachable),
+            plan(&start(), &Target::Stacks(target), &CrateMover9000).err()
+        );
+        assert_eq!(
+            Some(Error::Unreachable),
+            plan(
+                &Stacks::parse_drawing("[A]\n[B]\n 1   2 ").unwrap(),
+                &Target::TopCrates("AA".into()),
+                &CrateMover9000
+            )
+            .err()
+        );
+        assert_eq!(
+            Some(Error::SearchLimit { states: 10 }),
+            search(
+                &start(),
+                &Target::TopCrates("CMZ".into()),
+                &CrateMover9000,
+                10
+            )
+            .err()
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::{io::Write, thread, time::Duration};
+
+use crate::{crane::Crane, Error, Move, Stacks};
+
+/// Clears the terminal and moves the cursor to the top left corner.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// The stacks before the first move and after each move of a rearrangement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recording {
+    pub moves: Vec<Move>,
+    /// `states[0]` is the starting position and `states[n]` the stacks after the nth move.
+    states: Vec<Stacks>,
+}
+
+impl Recording {
+    /// Carries out `moves` on `stacks` with the given crane, keeping a copy of every state.
+    pub fn record(mut stacks: Stacks, moves: &[Move], crane: &dyn Crane) -> Result<Self, Error> {
+        let mut states = Vec::with_capacity(moves.len() + 1);
+        states.push(stacks.snapshot());
+        for step in moves {
+            stacks.apply(step, crane)?;
+            states.push(stacks.snapshot());
+        }
+        Ok(Self {
+            moves: moves.to_vec(),
+            states,
+        })
+    }
+
+    /// Number of moves recorded. Steps run from 0, the starting position, to this.
+    pub fn steps(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn state(&self, step: usize) -> Option<&Stacks> {
+        self.states.get(step)
+    }
+
+    /// The move that led to `step` followed by the drawing of the stacks.
+    pub fn frame(&self, step: usize) -> Option<String> {
+        let state = self.state(step)?;
+        let heading = match step.checked_sub(1) {
+            None => "starting position".to_string(),
+            Some(index) => self.moves[index].to_string(),
+        };
+        Some(format!(
+            "Step {step} of {}: {heading}\n{state}",
+            self.steps()
+        ))
+    }
+
+    /// Plays every frame in turn, clearing the screen before each and waiting `delay` after.
+    pub fn animate(&self, out: &mut impl Write, delay: Duration) -> std::io::Result<()> {
+        for step in 0..=self.steps() {
+            let frame = self
+                .frame(step)
+                .expect("every step up to steps() is recorded");
+            writeln!(out, "{CLEAR_SCREEN}{frame}")?;
+            out.flush()?;
+            if step < self.steps() {
+                thread::sleep(delay);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crane::CrateMover9000, parse_input};
+
+    const EXAMPLE: &str = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+
+    fn recording() -> Recording {
+        let (stacks, moves) = parse_input(EXAMPLE).unwrap();
+        Recording::record(stacks, &moves, &CrateMover9000).unwrap()
+    }
+
+    #[test]
+    fn record() {
+        let recording = recording();
+        assert_eq!(4, recording.steps());
+        assert_eq!("NDP", recording.state(0).unwrap().top_crates());
+        assert_eq!("CMZ", recording.state(4).unwrap().top_crates());
+        assert_eq!(None, recording.state(5));
+
+        assert_eq!(
+            "Step 0 of 4: starting position\n    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ",
+            recording.frame(0).unwrap()
+        );
+        assert_eq!(
+            "Step 1 of 4: move 1 from 2 to 1\n[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ",
+            recording.frame(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn animate() {
+        let recording = recording();
+        let mut out = Vec::new();
+        recording.animate(&mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(5, out.matches(CLEAR_SCREEN).count());
+        assert!(out.ends_with(&format!("{}\n", recording.frame(4).unwrap())));
+    }
+
+    #[test]
+    fn errors() {
+        let (stacks, _) = parse_input(EXAMPLE).unwrap();
+        let moves = [
+            "move 1 from 2 to 1".try_into().unwrap(),
+            "move 9 from 1 to 2".try_into().unwrap(),
+        ];
+        assert_eq!(
+            Some(Error::NotEnoughCrates {
+                requested: 9,
+                stack: 1,
+                available: 3
+            }),
+            Recording::record(stacks, &moves, &CrateMover9000).err()
+        );
+    }
+}
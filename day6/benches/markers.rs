@@ -0,0 +1,55 @@
+//! Compares the marker searches on a large random stream of lowercase letters. The stream is
+//! 2 GiB unless `DAY6_BENCH_BYTES` says otherwise.
+
+use std::{env, thread};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day6::{
+    find_marker,
+    search::{find_marker_parallel, find_marker_skipping},
+    START_OF_MESSAGE_LEN,
+};
+
+const DEFAULT_BYTES: usize = 2 << 30;
+
+/// Lowercase letters from an xorshift generator, so every run searches the same stream.
+fn random_stream(len: usize) -> Vec<u8> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            b'a' + (state % 26) as u8
+        })
+        .collect()
+}
+
+fn markers(c: &mut Criterion) {
+    let len = env::var("DAY6_BENCH_BYTES")
+        .ok()
+        .and_then(|bytes| bytes.parse().ok())
+        .unwrap_or(DEFAULT_BYTES);
+    let stream = random_stream(len);
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+
+    let mut group = c.benchmark_group("markers");
+    group
+        .sample_size(10)
+        .throughput(Throughput::Bytes(len as u64));
+    let window_len = START_OF_MESSAGE_LEN;
+    group.bench_function(BenchmarkId::new("scalar", window_len), |b| {
+        b.iter(|| find_marker(&stream, window_len))
+    });
+    group.bench_function(BenchmarkId::new("skipping", window_len), |b| {
+        b.iter(|| find_marker_skipping(&stream, window_len))
+    });
+    group.bench_function(
+        BenchmarkId::new(format!("parallel/{threads}"), window_len),
+        |b| b.iter(|| find_marker_parallel(&stream, window_len, threads)),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, markers);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use std::{env, fs::File, io};
+
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+use anyhow::{Context, Error, Result};
+use day6::{
+    detector::{MarkerDetector, MAX_PAYLOAD_LEN},
+    START_OF_MESSAGE_LEN, START_OF_PACKET_LEN,
+};
+
+const USAGE: &str = "\
+Usage:
+    day6-decode [--packet N] [--message N] [--max-payload N] [--socket PATH | PATH]
+
+Prints each start-of-packet and start-of-message marker as it arrives, along with the payload
+between them, reading from the Unix domain socket or the file or named pipe at PATH, or from stdin.
+Markers are N different characters long (default 4 and 14). Packets with more than N bytes of
+payload (default 1048576) are reported and skipped. --socket is only available on Unix.";
+
+fn main() -> Result<()> {
+    let mut packet_len = START_OF_PACKET_LEN;
+    let mut message_len = START_OF_MESSAGE_LEN;
+    let mut max_payload_len = MAX_PAYLOAD_LEN;
+    let mut reader: Option<Box<dyn io::Read>> = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--packet" | "--message" | "--max-payload" => {
+                let n = args.next().context(USAGE)?;
+                let n = n.parse().with_context(|| format!("{n} is not a valid N"))?;
+                match arg.as_str() {
+                    "--packet" => packet_len = n,
+                    "--message" => message_len = n,
+                    _ => max_payload_len = n,
+                }
+            }
+            #[cfg(unix)]
+            "--socket" if reader.is_none() => {
+                let path = args.next().context(USAGE)?;
+                let socket = UnixStream::connect(&path)
+                    .with_context(|| format!("could not connect to {path}"))?;
+                reader = Some(Box::new(socket));
+            }
+            path if reader.is_none() && !path.starts_with("--") => {
+                let file = File::open(path).with_context(|| format!("could not open {path}"))?;
+                reader = Some(Box::new(file));
+            }
+            _ => return Err(Error::msg(USAGE)),
+        }
+    }
+    let reader = reader.unwrap_or_else(|| Box::new(io::stdin().lock()));
+
+    let detector =
+        MarkerDetector::new(packet_len, message_len).with_max_payload_len(max_payload_len);
+    for event in detector.events(reader) {
+        match event {
+            Ok(event) => println!("{event}"),
+            // Oversized packets are skipped rather than ending the stream.
+            Err(error) if error.kind() == io::ErrorKind::InvalidData => eprintln!("{error}"),
+            Err(error) => return Err(error.into()),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,347 @@
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    io::{self, BufReader, Bytes, Read},
+};
+
+use crate::{Error, START_OF_MESSAGE_LEN, START_OF_PACKET_LEN};
+
+/// Longest payload a packet may have before the detector gives up on it, by default.
+pub const MAX_PAYLOAD_LEN: usize = 1 << 20;
+
+/// Something the detector found in the datastream. Positions count every byte pushed so far,
+/// starting from 1, like the puzzle answers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    StartOfPacket {
+        position: usize,
+    },
+    /// The message marker that follows a start-of-packet marker, and the packet's payload: every
+    /// byte after the start-of-packet marker up to and including this one.
+    StartOfMessage {
+        position: usize,
+        payload: Vec<u8>,
+    },
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::StartOfPacket { position } => write!(f, "start-of-packet after {position}"),
+            Event::StartOfMessage { position, payload } => write!(
+                f,
+                "start-of-message after {position}, payload {:?}",
+                String::from_utf8_lossy(payload)
+            ),
+        }
+    }
+}
+
+/// The last `len` bytes, with a count of each byte and of how many appear more than once.
+#[derive(Clone, Debug)]
+struct Window {
+    len: usize,
+    bytes: VecDeque<u8>,
+    counts: [usize; 256],
+    duplicates: usize,
+}
+
+impl Window {
+    fn new(len: usize) -> Self {
+        Self {
+            len,
+            bytes: VecDeque::with_capacity(len + 1),
+            counts: [0; 256],
+            duplicates: 0,
+        }
+    }
+
+    /// Adds a byte and returns whether the window is now full of different bytes.
+    fn push(&mut self, byte: u8) -> bool {
+        self.bytes.push_back(byte);
+        self.counts[usize::from(byte)] += 1;
+        if self.counts[usize::from(byte)] == 2 {
+            self.duplicates += 1;
+        }
+        if self.bytes.len() > self.len {
+            let old = usize::from(self.bytes.pop_front().expect("the window is not empty"));
+            if self.counts[old] == 2 {
+                self.duplicates -= 1;
+            }
+            self.counts[old] -= 1;
+        }
+        self.bytes.len() == self.len && self.duplicates == 0
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new(self.len);
+    }
+}
+
+/// Finds markers in a datastream fed one byte at a time. It waits for a start-of-packet marker,
+/// then collects the packet until a start-of-message marker, then starts over with the bytes
+/// that follow, so the stream can go on forever. Packets longer than the payload limit are
+/// dropped, so memory use stays bounded.
+#[derive(Clone, Debug)]
+pub struct MarkerDetector {
+    packet: Window,
+    message: Window,
+    position: usize,
+    /// The bytes of the current packet, once its start-of-packet marker has been seen.
+    payload: Option<Vec<u8>>,
+    max_payload_len: usize,
+}
+
+impl Default for MarkerDetector {
+    fn default() -> Self {
+        Self::new(START_OF_PACKET_LEN, START_OF_MESSAGE_LEN)
+    }
+}
+
+impl MarkerDetector {
+    pub fn new(packet_len: usize, message_len: usize) -> Self {
+        Self {
+            packet: Window::new(packet_len),
+            message: Window::new(message_len),
+            position: 0,
+            payload: None,
+            max_payload_len: MAX_PAYLOAD_LEN,
+        }
+    }
+
+    pub fn with_max_payload_len(mut self, max_payload_len: usize) -> Self {
+        self.max_payload_len = max_payload_len;
+        self
+    }
+
+    /// Number of bytes pushed so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Adds a byte, returning the events it completes: usually none, but both markers can
+    /// complete on the same byte when the message marker is no longer than the packet marker.
+    /// A packet whose payload fills the limit without reaching a message marker is dropped with
+    /// an error, and the detector waits for the next start-of-packet marker.
+    pub fn push(&mut self, byte: u8) -> Result<Vec<Event>, Error> {
+        self.position += 1;
+        let packet_marker = self.packet.push(byte);
+        let message_marker = self.message.push(byte);
+        let mut events = Vec::new();
+        match &mut self.payload {
+            None if packet_marker => {
+                self.payload = Some(Vec::new());
+                events.push(Event::StartOfPacket {
+                    position: self.position,
+                });
+            }
+            None => return Ok(events),
+            Some(payload) => payload.push(byte),
+        }
+        // The byte that completes the message marker ends the packet, so a payload that only
+        // reaches the limit with it is still delivered.
+        if message_marker {
+            let payload = self.payload.take().expect("a packet has started");
+            self.reset();
+            events.push(Event::StartOfMessage {
+                position: self.position,
+                payload,
+            });
+        } else if self
+            .payload
+            .as_ref()
+            .is_some_and(|payload| payload.len() >= self.max_payload_len)
+        {
+            self.reset();
+            return Err(Error::PayloadTooLong {
+                limit: self.max_payload_len,
+                position: self.position,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Forgets the current packet and the bytes seen so far.
+    fn reset(&mut self) {
+        self.payload = None;
+        self.packet.clear();
+        self.message.clear();
+    }
+
+    /// Feeds every byte read from `reader` through the detector as it arrives, yielding the
+    /// events found.
+    /// Payloads that are too long are reported as [`io::ErrorKind::InvalidData`] errors, and
+    /// the events carry on after them.
+    pub fn events<R: Read>(self, reader: R) -> Events<R> {
+        Events {
+            bytes: BufReader::new(reader).bytes(),
+            detector: self,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+/// The events found in a reader, from [`MarkerDetector::events`].
+#[derive(Debug)]
+pub struct Events<R> {
+    bytes: Bytes<BufReader<R>>,
+    detector: MarkerDetector,
+    /// Events completed by the last byte that haven't been returned yet.
+    pending: VecDeque<Event>,
+}
+
+impl<R: Read> Iterator for Events<R> {
+    type Item = io::Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            match self.bytes.next()? {
+                Ok(byte) => match self.detector.push(byte) {
+                    Ok(events) => self.pending.extend(events),
+                    Err(error) => {
+                        return Some(Err(io::Error::new(io::ErrorKind::InvalidData, error)))
+                    }
+                },
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (input, packet, message) in examples {
+            let events: Vec<Event> = MarkerDetector::default()
+                .events(input.as_bytes())
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(
+                Event::StartOfPacket { position: packet },
+                events[0],
+                "{input}"
+            );
+            assert_eq!(
+                Event::StartOfMessage {
+                    position: message,
+                    payload: input.as_bytes()[packet..message].to_vec()
+                },
+                events[1],
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn starts_over_after_each_message() {
+        let mut detector = MarkerDetector::new(2, 3);
+        let events: Vec<Event> = b"aabaabcabba"
+            .iter()
+            .flat_map(|&byte| detector.push(byte).unwrap())
+            .collect();
+        assert_eq!(
+            vec![
+                Event::StartOfPacket { position: 3 },
+                Event::StartOfMessage {
+                    position: 7,
+                    payload: b"aabc".to_vec()
+                },
+                Event::StartOfPacket { position: 9 },
+            ],
+            events
+        );
+        assert_eq!(11, detector.position());
+    }
+
+    #[test]
+    fn markers_on_the_same_byte() {
+        let mut detector = MarkerDetector::new(3, 2);
+        assert_eq!(Vec::<Event>::new(), detector.push(b'a').unwrap());
+        assert_eq!(Vec::<Event>::new(), detector.push(b'b').unwrap());
+        assert_eq!(
+            vec![
+                Event::StartOfPacket { position: 3 },
+                Event::StartOfMessage {
+                    position: 3,
+                    payload: Vec::new()
+                },
+            ],
+            detector.push(b'c').unwrap()
+        );
+    }
+
+    #[test]
+    fn payload_limit() {
+        let mut detector = MarkerDetector::new(2, 4).with_max_payload_len(3);
+        let results: Vec<Result<Vec<Event>, Error>> = b"abaaaabc"
+            .iter()
+            .map(|&byte| detector.push(byte))
+            .collect();
+        assert_eq!(Ok(vec![Event::StartOfPacket { position: 2 }]), results[1]);
+        assert_eq!(
+            Err(Error::PayloadTooLong {
+                limit: 3,
+                position: 5
+            }),
+            results[4]
+        );
+        // The window starts afresh after the dropped packet.
+        assert_eq!(Ok(Vec::new()), results[5]);
+        assert_eq!(Ok(vec![Event::StartOfPacket { position: 7 }]), results[6]);
+
+        let mut events = MarkerDetector::new(2, 4)
+            .with_max_payload_len(3)
+            .events(&b"abaaaabcd"[..]);
+        assert!(events.next().unwrap().is_ok());
+        assert_eq!(
+            io::ErrorKind::InvalidData,
+            events.next().unwrap().unwrap_err().kind()
+        );
+        assert_eq!(
+            Event::StartOfPacket { position: 7 },
+            events.next().unwrap().unwrap()
+        );
+    }
+
+    #[test]
+    fn message_at_payload_limit() {
+        let mut detector = MarkerDetector::new(2, 4).with_max_payload_len(3);
+        let results: Vec<Result<Vec<Event>, Error>> =
+            b"abacd".iter().map(|&byte| detector.push(byte)).collect();
+        assert_eq!(
+            Ok(vec![Event::StartOfMessage {
+                position: 5,
+                payload: b"acd".to_vec()
+            }]),
+            results[4]
+        );
+    }
+
+    #[test]
+    fn read_errors() {
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("pipe closed"))
+            }
+        }
+        let mut events = MarkerDetector::default().events(Broken);
+        assert_eq!(
+            "pipe closed",
+            events.next().unwrap().unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use common::Solver;
+use thiserror::Error;
+
+pub mod detector;
+pub mod search;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("no window of {window_len} distinct characters found in the datastream")]
+    NoMarker { window_len: usize },
+    #[error(
+        "byte {position}: packet payload passed {limit} bytes without a start-of-message marker"
+    )]
+    PayloadTooLong { limit: usize, position: usize },
+}
+
+pub const START_OF_PACKET_LEN: usize = 4;
+pub const START_OF_MESSAGE_LEN: usize = 14;
+
+/// Yields the 1-based number of bytes processed each time the last `window_len` bytes are all
+/// different, in O(n) by keeping a count of each byte in the window and of how many bytes appear
+/// in it more than once.
+fn markers(stream: &[u8], window_len: usize) -> impl Iterator<Item = usize> + '_ {
+    let mut counts = [0usize; 256];
+    let mut duplicates = 0;
+    stream.iter().enumerate().filter_map(move |(i, &byte)| {
+        counts[usize::from(byte)] += 1;
+        if counts[usize::from(byte)] == 2 {
+            duplicates += 1;
+        }
+        if let Some(left) = i.checked_sub(window_len) {
+            let old = usize::from(stream[left]);
+            if counts[old] == 2 {
+                duplicates -= 1;
+            }
+            counts[old] -= 1;
+        }
+        // +1 since the answer is 1-based
+        (i + 1 >= window_len && duplicates == 0).then_some(i + 1)
+    })
+}
+
+/// Every marker in the stream: the 1-based number of bytes processed whenever the last
+/// `window_len` bytes are all different.
+pub fn find_marker(stream: &[u8], window_len: usize) -> Vec<usize> {
+    markers(stream, window_len).collect()
+}
+
+/// Returns the 1-based number of bytes processed once the last `window_len` bytes are all
+/// different for the first time.
+pub fn marker_position(stream: &[u8], window_len: usize) -> Option<usize> {
+    markers(stream, window_len).next()
+}
+
+/// Characters processed before the first start-of-packet marker.
+pub fn solve_part1(input: &str) -> Result<usize, Error> {
+    marker_position(input.trim_end().as_bytes(), START_OF_PACKET_LEN).ok_or(Error::NoMarker {
+        window_len: START_OF_PACKET_LEN,
+    })
+}
+
+/// Characters processed before the first start-of-message marker.
+pub fn solve_part2(input: &str) -> Result<usize, Error> {
+    marker_position(input.trim_end().as_bytes(), START_OF_MESSAGE_LEN).ok_or(Error::NoMarker {
+        window_len: START_OF_MESSAGE_LEN,
+    })
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn part1(input: &str) -> anyhow::Result<usize> {
+        Ok(solve_part1(input)?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<usize> {
+        Ok(solve_part2(input)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (input, packet, message) in examples {
+            assert_eq!(packet, solve_part1(input).unwrap());
+            assert_eq!(message, solve_part2(input).unwrap());
+        }
+    }
+
+    #[test]
+    fn all_markers() {
+        assert_eq!(vec![7, 8, 9, 10, 11, 12], find_marker(b"mjqjpqmgbljs", 4));
+        assert_eq!(vec![3, 4, 5], find_marker(b"abcabba", 3));
+        assert_eq!(vec![1, 2, 3], find_marker(b"aaa", 1));
+        assert!(find_marker(b"abc", 4).is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Some(Error::NoMarker { window_len: 4 }),
+            solve_part1("abcabcabc").err()
+        );
+    }
+}
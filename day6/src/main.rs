@@ -0,0 +1,57 @@
+use std::env;
+
+use anyhow::{Context, Error, Result};
+use day6::{find_marker, marker_position, START_OF_MESSAGE_LEN, START_OF_PACKET_LEN};
+
+const USAGE: &str = "\
+Usage:
+    day6
+    day6 --window N [--all]
+
+Without options, prints how many characters arrive before the first start-of-packet and
+start-of-message markers. --window prints how many arrive before the last N are all different
+instead, and --all lists every such position rather than just the first.";
+
+fn main() -> Result<()> {
+    let mut window_len = None;
+    let mut all = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--window" => {
+                let n = args.next().context(USAGE)?;
+                window_len = Some(n.parse().with_context(|| format!("{n} is not a valid N"))?);
+            }
+            "--all" => all = true,
+            _ => return Err(Error::msg(USAGE)),
+        }
+    }
+
+    let buffer = common::read_stdin()?;
+    let stream = buffer.trim_end().as_bytes();
+    let Some(window_len) = window_len else {
+        if all {
+            return Err(Error::msg(USAGE));
+        }
+        for (marker, window_len) in [
+            ("start-of-packet", START_OF_PACKET_LEN),
+            ("start-of-message", START_OF_MESSAGE_LEN),
+        ] {
+            let position =
+                marker_position(stream, window_len).ok_or(day6::Error::NoMarker { window_len })?;
+            println!("The {marker} marker appears after character {position} arrives");
+        }
+        return Ok(());
+    };
+
+    if all {
+        for position in find_marker(stream, window_len) {
+            println!("{position}");
+        }
+        return Ok(());
+    }
+    let position =
+        marker_position(stream, window_len).ok_or(day6::Error::NoMarker { window_len })?;
+    println!("Marker appears after character {position} arrives");
+    Ok(())
+}
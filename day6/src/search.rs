@@ -0,0 +1,121 @@
+use std::thread;
+
+/// A set of bytes, one bit each.
+#[derive(Clone, Copy, Debug, Default)]
+struct Mask([u64; 4]);
+
+impl Mask {
+    fn contains(&self, byte: u8) -> bool {
+        self.0[usize::from(byte >> 6)] & (1 << (byte & 63)) != 0
+    }
+
+    /// Flips the bit for `byte`, adding it if it was missing and removing it if it was there.
+    fn toggle(&mut self, byte: u8) {
+        self.0[usize::from(byte >> 6)] ^= 1 << (byte & 63);
+    }
+}
+
+/// The same markers as [`find_marker`](crate::find_marker), found by checking each window from
+/// its last byte backwards. A repeated byte means no window that still contains its earlier copy
+/// can be a marker, so the search jumps to the window starting just after it. After a marker the
+/// window slides one byte at a time, XORing the byte that leaves out of the mask.
+pub fn find_marker_skipping(stream: &[u8], window_len: usize) -> Vec<usize> {
+    if window_len == 0 {
+        return (1..=stream.len()).collect();
+    }
+    let mut markers = Vec::new();
+    let mut start = 0;
+    'windows: while start + window_len <= stream.len() {
+        let end = start + window_len;
+        let mut mask = Mask::default();
+        for (index, &byte) in stream[start..end].iter().enumerate().rev() {
+            if mask.contains(byte) {
+                start += index + 1;
+                continue 'windows;
+            }
+            mask.toggle(byte);
+        }
+        markers.push(end);
+
+        // Slide while the window stays a marker. The window holds no repeats, so XORing out the
+        // byte that leaves removes it.
+        for end in end..stream.len() {
+            mask.toggle(stream[end - window_len]);
+            start = end + 1 - window_len;
+            if mask.contains(stream[end]) {
+                continue 'windows;
+            }
+            mask.toggle(stream[end]);
+            markers.push(end + 1);
+        }
+        break;
+    }
+    markers
+}
+
+/// The same markers as [`find_marker`](crate::find_marker), found by splitting the stream into
+/// one chunk per thread. Each chunk also covers the `window_len - 1` bytes before it, so markers
+/// whose window crosses into it from the previous chunk are still found.
+pub fn find_marker_parallel(stream: &[u8], window_len: usize, threads: usize) -> Vec<usize> {
+    let chunk_len = stream.len().div_ceil(threads.max(1)).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..stream.len())
+            .step_by(chunk_len)
+            .map(|low| {
+                let high = (low + chunk_len).min(stream.len());
+                let offset = low.saturating_sub(window_len.saturating_sub(1));
+                scope.spawn(move || {
+                    let mut markers = find_marker_skipping(&stream[offset..high], window_len);
+                    // Markers ending before this chunk belong to the previous one.
+                    markers.retain(|&position| offset + position > low);
+                    markers.iter_mut().for_each(|position| *position += offset);
+                    markers
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("marker search thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find_marker;
+
+    /// A deterministic stream of lowercase letters.
+    fn stream(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                b'a' + (state % 26) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_scalar() {
+        let examples: [&[u8]; 4] = [b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", b"abcabba", b"aaa", b""];
+        for example in examples {
+            for window_len in 0..6 {
+                let expected = find_marker(example, window_len);
+                assert_eq!(expected, find_marker_skipping(example, window_len));
+                assert_eq!(expected, find_marker_parallel(example, window_len, 3));
+            }
+        }
+
+        let stream = stream(10_000);
+        for window_len in [1, 4, 8, 14] {
+            let expected = find_marker(&stream, window_len);
+            assert_eq!(expected, find_marker_skipping(&stream, window_len));
+            for threads in [1, 2, 7, 64] {
+                assert_eq!(expected, find_marker_parallel(&stream, window_len, threads));
+            }
+        }
+    }
+}
@@ -1,28 +1,62 @@
 use std::{collections::HashMap, path::PathBuf};
 
-use anyhow::{Context, Error, Result};
+use common::Solver;
+use thiserror::Error;
+use transcript::Transcript;
 
-#[derive(Debug)]
+pub mod query;
+pub mod transcript;
+
+/// Directories at most this size count towards the part 1 answer.
+pub const SMALL_DIRECTORY_SIZE: usize = 100000;
+const TOTAL_DISK_SPACE: usize = 70000000;
+const REQUIRED_FREE_SPACE: usize = 30000000;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("{name:?} is a file, not a directory")]
+    NotADirectory { name: String },
+    #[error("no directory named {name:?}")]
+    NoSuchDirectory { name: String },
+    #[error("line {line}: {output:?} is neither a directory nor a file listing")]
+    MalformedOutput { output: String, line: usize },
+    #[error("line {line}: {token:?} is not a valid file size")]
+    InvalidFileSize { token: String, line: usize },
+    #[error("line {line}: expected a command, got {output:?}")]
+    UnexpectedOutput { output: String, line: usize },
+    #[error("line {line}: {command:?} is not `cd PATH` or `ls`")]
+    UnknownCommand { command: String, line: usize },
+    #[error("line {line}: {name:?} is a file, not a directory")]
+    FileInPath { name: String, line: usize },
+    #[error("line {line}: {name:?} does not match what an earlier listing showed")]
+    Conflict { name: String, line: usize },
+    #[error("{used} bytes are in use, more than the disk holds")]
+    DiskOverfull { used: usize },
+    #[error("no directory of at least size {required} found")]
+    NoDirectoryLargeEnough { required: usize },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Node {
     File(usize),
     Directory(HashMap<String, Node>),
 }
 
 impl Node {
-    pub fn cd(&mut self, name: &str) -> Result<&mut Self> {
+    pub fn cd(&mut self, name: &str) -> Result<&mut Self, Error> {
         match self {
-            Node::File(_) => {
-                panic!("cannot cd from a file")
-            }
-            Node::Directory(children) => {
-                children.get_mut(name).context("retrieving child directory")
-            }
+            Node::File(_) => Err(Error::NotADirectory { name: name.into() }),
+            Node::Directory(children) => match children.get_mut(name) {
+                Some(Node::File(_)) => Err(Error::NotADirectory { name: name.into() }),
+                Some(child) => Ok(child),
+                None => Err(Error::NoSuchDirectory { name: name.into() }),
+            },
         }
     }
 
-    pub fn add_child(&mut self, name: &str, node: Node) -> Result<()> {
+    pub fn add_child(&mut self, name: &str, node: Node) -> Result<(), Error> {
         match self {
-            Node::File(_) => Err(Error::msg("cannot add child to a file")),
+            Node::File(_) => Err(Error::NotADirectory { name: name.into() }),
             Node::Directory(children) => {
                 children.insert(name.into(), node);
                 Ok(())
@@ -34,61 +68,128 @@ impl Node {
         Self::Directory(HashMap::new())
     }
 
-    pub fn navigate_to(&mut self, path: &PathBuf) -> Result<&mut Self> {
+    pub fn navigate_to(&mut self, path: &PathBuf) -> Result<&mut Self, Error> {
         let mut current = self;
         for directory in path {
             if directory == "/" {
                 continue;
             }
-            current = current
-                .cd(directory.to_str().context("directory to string")?)
-                .context("changing into directory")?;
+            current = current.cd(&directory.to_string_lossy())?;
         }
         Ok(current)
     }
 
+    /// Total size of everything under this node. Use [`Node::sizes`] to measure every
+    /// directory at once.
     pub fn size(&self) -> usize {
         match self {
             Node::File(size) => *size,
-            Node::Directory(children) => children.values().fold(0, |acc, e| acc + e.size()),
+            Node::Directory(children) => children.values().map(Node::size).sum(),
         }
     }
+}
 
-    pub fn solve(&self) -> usize {
-        let mut result = 0;
-        if let Node::Directory(children) = self {
-            let size = self.size();
-            if size <= 100000 {
-                result += size;
-            }
-            for child in children.values() {
-                result += child.solve();
-            }
-        }
-        result
+impl TryFrom<&str> for Node {
+    type Error = Error;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Transcript::parse(input)?.replay()
     }
+}
 
-    pub fn smallest_dir_size_of_at_least(
-        &self,
-        minimum_size: usize,
-        mut smallest: Option<usize>,
-    ) -> Option<usize> {
-        if let Node::Directory(children) = self {
-            let size = self.size();
-            if size >= minimum_size {
-                match smallest {
-                    Some(current_smallest) => {
-                        if size < current_smallest {
-                            smallest = Some(size)
-                        }
-                    }
-                    None => smallest = Some(size),
-                }
-            }
-            for child in children.values() {
-                smallest = child.smallest_dir_size_of_at_least(minimum_size, smallest)
-            }
-        }
-        smallest
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn part1(input: &str) -> anyhow::Result<usize> {
+        let root = Node::try_from(input)?;
+        Ok(root
+            .sizes()
+            .filter(|measured| measured.is_directory() && measured.size <= SMALL_DIRECTORY_SIZE)
+            .map(|directory| directory.size)
+            .sum())
+    }
+
+    fn part2(input: &str) -> anyhow::Result<usize> {
+        let root = Node::try_from(input)?;
+        let sizes = root.sizes();
+        let used = sizes.total();
+        let free_space = TOTAL_DISK_SPACE
+            .checked_sub(used)
+            .ok_or(Error::DiskOverfull { used })?;
+        let extra_space_required = REQUIRED_FREE_SPACE.saturating_sub(free_space);
+
+        Ok(sizes
+            .directories()
+            .map(|directory| directory.size)
+            .filter(|&size| size >= extra_space_required)
+            .min()
+            .ok_or(Error::NoDirectoryLargeEnough {
+                required: extra_space_required,
+            })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "$ cd /\n",
+        "$ ls\n",
+        "dir a\n",
+        "14848514 b.txt\n",
+        "8504156 c.dat\n",
+        "dir d\n",
+        "$ cd a\n",
+        "$ ls\n",
+        "dir e\n",
+        "29116 f\n",
+        "2557 g\n",
+        "62596 h.lst\n",
+        "$ cd e\n",
+        "$ ls\n",
+        "584 i\n",
+        "$ cd ..\n",
+        "$ cd ..\n",
+        "$ cd d\n",
+        "$ ls\n",
+        "4060174 j\n",
+        "8033020 d.log\n",
+        "5626152 d.ext\n",
+        "7214296 k\n",
+    );
+
+    #[test]
+    fn example() {
+        assert_eq!(95437, Day7::part1(EXAMPLE).unwrap());
+        assert_eq!(24933642, Day7::part2(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Some(Error::MalformedOutput {
+                output: "b.txt".into(),
+                line: 3
+            }),
+            Node::try_from("$ cd /\n$ ls\nb.txt").err()
+        );
+        assert_eq!(
+            Some(Error::InvalidFileSize {
+                token: "12x".into(),
+                line: 3
+            }),
+            Node::try_from("$ cd /\n$ ls\n12x b.txt").err()
+        );
+        assert_eq!(
+            Some(Error::FileInPath {
+                name: "b.txt".into(),
+                line: 4
+            }),
+            Node::try_from("$ cd /\n$ ls\n12 b.txt\n$ cd b.txt").err()
+        );
     }
 }
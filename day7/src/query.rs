@@ -0,0 +1,213 @@
+use std::path::Path;
+
+use crate::Node;
+
+/// A file or directory in the tree, with its total size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Measured<'a> {
+    pub node: &'a Node,
+    pub size: usize,
+    /// Number of directories between the root and this node, so 0 for the root.
+    pub depth: usize,
+    path: &'a str,
+}
+
+impl<'a> Measured<'a> {
+    /// Absolute path, such as `/` or `/a/e/i`.
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+
+    pub fn is_directory(&self) -> bool {
+        matches!(self.node, Node::Directory(_))
+    }
+
+    fn name(&self) -> &'a str {
+        self.path.rsplit('/').next().unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Record<'a> {
+    path: String,
+    node: &'a Node,
+    size: usize,
+    depth: usize,
+}
+
+/// Every node of a tree with its size, worked out in a single pass so each directory is only
+/// added up once. Nodes are listed parent first, with children in name order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sizes<'a> {
+    records: Vec<Record<'a>>,
+}
+
+impl Node {
+    pub fn sizes(&self) -> Sizes<'_> {
+        let mut sizes = Sizes {
+            records: Vec::new(),
+        };
+        sizes.measure(self, "/".into(), 0);
+        sizes
+    }
+}
+
+impl<'a> Sizes<'a> {
+    fn measure(&mut self, node: &'a Node, path: String, depth: usize) -> usize {
+        let index = self.records.len();
+        let prefix = if depth == 0 {
+            String::new()
+        } else {
+            path.clone()
+        };
+        self.records.push(Record {
+            path,
+            node,
+            size: 0,
+            depth,
+        });
+        let size = match node {
+            Node::File(size) => *size,
+            Node::Directory(children) => {
+                let mut names: Vec<&String> = children.keys().collect();
+                names.sort_unstable();
+                names
+                    .into_iter()
+                    .map(|name| {
+                        self.measure(&children[name], format!("{prefix}/{name}"), depth + 1)
+                    })
+                    .sum()
+            }
+        };
+        self.records[index].size = size;
+        size
+    }
+
+    /// Total size of the tree.
+    pub fn total(&self) -> usize {
+        self.records.first().map_or(0, |record| record.size)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Measured<'_>> {
+        self.records.iter().map(|record| Measured {
+            node: record.node,
+            size: record.size,
+            depth: record.depth,
+            path: &record.path,
+        })
+    }
+
+    pub fn filter<'s, P>(&'s self, mut predicate: P) -> impl Iterator<Item = Measured<'s>>
+    where
+        P: FnMut(&Measured<'s>) -> bool,
+    {
+        self.iter().filter(move |measured| predicate(measured))
+    }
+
+    pub fn directories(&self) -> impl Iterator<Item = Measured<'_>> {
+        self.filter(Measured::is_directory)
+    }
+
+    /// The `n` largest directories, largest first, with ties in path order.
+    pub fn largest_directories(&self, n: usize) -> Vec<Measured<'_>> {
+        let mut directories: Vec<Measured> = self.directories().collect();
+        directories.sort_by_key(|directory| std::cmp::Reverse(directory.size));
+        directories.truncate(n);
+        directories
+    }
+
+    /// Files whose name ends in `.extension`.
+    pub fn files_with_extension<'s>(
+        &'s self,
+        extension: &'s str,
+    ) -> impl Iterator<Item = Measured<'s>> {
+        self.filter(move |measured| {
+            !measured.is_directory()
+                && Path::new(measured.name())
+                    .extension()
+                    .is_some_and(|found| found == extension)
+        })
+    }
+
+    /// The node furthest from the root, the first in path order if there is a tie.
+    pub fn deepest(&self) -> Option<Measured<'_>> {
+        self.iter().reduce(|deepest, measured| {
+            if measured.depth > deepest.depth {
+                measured
+            } else {
+                deepest
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "$ cd /\n",
+        "$ ls\n",
+        "dir a\n",
+        "14848514 b.txt\n",
+        "8504156 c.dat\n",
+        "dir d\n",
+        "$ cd a\n",
+        "$ ls\n",
+        "dir e\n",
+        "29116 f\n",
+        "2557 g\n",
+        "62596 h.lst\n",
+        "$ cd e\n",
+        "$ ls\n",
+        "584 i\n",
+        "$ cd ..\n",
+        "$ cd ..\n",
+        "$ cd d\n",
+        "$ ls\n",
+        "4060174 j\n",
+        "8033020 d.log\n",
+        "5626152 d.ext\n",
+        "7214296 k\n",
+    );
+
+    #[test]
+    fn queries() {
+        let root = Node::try_from(EXAMPLE).unwrap();
+        let sizes = root.sizes();
+        assert_eq!(48381165, sizes.total());
+
+        let directories: Vec<(&str, usize)> = sizes
+            .directories()
+            .map(|directory| (directory.path(), directory.size))
+            .collect();
+        assert_eq!(
+            vec![
+                ("/", 48381165),
+                ("/a", 94853),
+                ("/a/e", 584),
+                ("/d", 24933642)
+            ],
+            directories
+        );
+
+        let largest: Vec<&str> = sizes
+            .largest_directories(2)
+            .iter()
+            .map(Measured::path)
+            .collect();
+        assert_eq!(vec!["/", "/d"], largest);
+
+        let logs: Vec<&str> = sizes
+            .files_with_extension("log")
+            .map(|file| file.path())
+            .collect();
+        assert_eq!(vec!["/d/d.log"], logs);
+
+        let deepest = sizes.deepest().unwrap();
+        assert_eq!(
+            ("/a/e/i", 3, 584),
+            (deepest.path(), deepest.depth, deepest.size)
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use crate::{Error, Node};
+
+/// A line of `ls` output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Entry {
+    Directory(String),
+    File { name: String, size: usize },
+}
+
+impl Entry {
+    fn parse(output: &str, line: usize) -> Result<Self, Error> {
+        let malformed = || Error::MalformedOutput {
+            output: output.into(),
+            line,
+        };
+        match output.split_once(' ').ok_or_else(malformed)? {
+            (_, "") => Err(malformed()),
+            ("dir", name) => Ok(Entry::Directory(name.into())),
+            (size, name) => {
+                let size = size.parse().map_err(|_| Error::InvalidFileSize {
+                    token: size.into(),
+                    line,
+                })?;
+                Ok(Entry::File {
+                    name: name.into(),
+                    size,
+                })
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Entry::Directory(name) | Entry::File { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Changes to `/`, `..`, a directory name or a path of them separated by `/`. Paths starting
+    /// with `/` are absolute.
+    Cd(String),
+    /// Lists the current directory, with each line of output and its line number.
+    Ls(Vec<(usize, Entry)>),
+}
+
+/// The commands of a terminal session, each with the line number it was typed on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Transcript {
+    pub commands: Vec<(usize, Command)>,
+}
+
+impl Transcript {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut commands = Vec::new();
+        for (line, output) in (1..).zip(input.lines()) {
+            let Some(command) = output.strip_prefix("$ ") else {
+                match commands.last_mut() {
+                    Some((_, Command::Ls(entries))) => {
+                        entries.push((line, Entry::parse(output, line)?));
+                    }
+                    _ => {
+                        return Err(Error::UnexpectedOutput {
+                            output: output.into(),
+                            line,
+                        })
+                    }
+                }
+                continue;
+            };
+            let command = match command.split_whitespace().collect::<Vec<_>>()[..] {
+                ["cd", path] => Command::Cd(path.into()),
+                ["ls"] => Command::Ls(Vec::new()),
+                _ => {
+                    return Err(Error::UnknownCommand {
+                        command: command.into(),
+                        line,
+                    })
+                }
+            };
+            commands.push((line, command));
+        }
+        Ok(Self { commands })
+    }
+
+    /// Rebuilds the filesystem the session explored. Changing into a directory that hasn't been
+    /// listed yet creates it, and listing a directory again is fine as long as the listings
+    /// agree.
+    pub fn replay(&self) -> Result<Node, Error> {
+        let mut root = Node::directory();
+        let mut current_path: Vec<&str> = Vec::new();
+        for (line, command) in &self.commands {
+            match command {
+                Command::Cd(path) => {
+                    if path.starts_with('/') {
+                        current_path.clear();
+                    }
+                    for name in path.split('/') {
+                        match name {
+                            "" | "." => {}
+                            ".." => {
+                                current_path.pop();
+                            }
+                            name => {
+                                current_path.push(name);
+                                root.create_path(&current_path, *line)?;
+                            }
+                        }
+                    }
+                }
+                Command::Ls(entries) => {
+                    let directory = root.create_path(&current_path, *line)?;
+                    for (line, entry) in entries {
+                        directory.add_listed(entry, *line)?;
+                    }
+                }
+            }
+        }
+        Ok(root)
+    }
+}
+
+impl Node {
+    /// Follows `path` from this directory, creating any directories that are missing. Errors
+    /// are reported against the transcript line that named the path.
+    fn create_path(&mut self, path: &[&str], line: usize) -> Result<&mut Self, Error> {
+        let mut current = self;
+        for name in path {
+            current = match current {
+                Node::File(_) => {
+                    return Err(Error::FileInPath {
+                        name: name.to_string(),
+                        line,
+                    })
+                }
+                Node::Directory(children) => children
+                    .entry(name.to_string())
+                    .or_insert_with(Node::directory),
+            };
+            if let Node::File(_) = current {
+                return Err(Error::FileInPath {
+                    name: name.to_string(),
+                    line,
+                });
+            }
+        }
+        Ok(current)
+    }
+
+    /// Adds an entry from an `ls` of this directory, checking it against anything already known
+    /// by that name.
+    fn add_listed(&mut self, entry: &Entry, line: usize) -> Result<(), Error> {
+        let Node::Directory(children) = self else {
+            return Err(Error::FileInPath {
+                name: entry.name().into(),
+                line,
+            });
+        };
+        let listed = match entry {
+            Entry::Directory(_) => Node::directory(),
+            Entry::File { size, .. } => Node::File(*size),
+        };
+        match (children.get(entry.name()), &listed) {
+            (None, _) => {
+                children.insert(entry.name().into(), listed);
+                Ok(())
+            }
+            (Some(Node::Directory(_)), Node::Directory(_)) => Ok(()),
+            (Some(Node::File(known)), Node::File(size)) if known == size => Ok(()),
+            (Some(_), _) => Err(Error::Conflict {
+                name: entry.name().into(),
+                line,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let transcript = Transcript::parse("$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ cd a").unwrap();
+        assert_eq!(
+            vec![
+                (1, Command::Cd("/".into())),
+                (
+                    2,
+                    Command::Ls(vec![
+                        (3, Entry::Directory("a".into())),
+                        (
+                            4,
+                            Entry::File {
+                                name: "b.txt".into(),
+                                size: 14848514
+                            }
+                        ),
+                    ])
+                ),
+                (5, Command::Cd("a".into())),
+            ],
+            transcript.commands
+        );
+    }
+
+    #[test]
+    fn replay() {
+        let input =
+            "$ cd a/b\n$ ls\n10 c\n$ cd /\n$ ls\n5 d\ndir a\n$ ls\n5 d\n$ cd /a/b/../e\n$ ls\n1 f";
+        let mut root = Transcript::parse(input).unwrap().replay().unwrap();
+        assert_eq!(16, root.size());
+        assert_eq!(10, root.cd("a").unwrap().cd("b").unwrap().size());
+        assert_eq!(1, root.cd("a").unwrap().cd("e").unwrap().size());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Some(Error::UnexpectedOutput {
+                output: "dir a".into(),
+                line: 2
+            }),
+            Transcript::parse("$ cd /\ndir a").err()
+        );
+        assert_eq!(
+            Some(Error::UnknownCommand {
+                command: "rm -rf a".into(),
+                line: 1
+            }),
+            Transcript::parse("$ rm -rf a").err()
+        );
+        assert_eq!(
+            Some(Error::MalformedOutput {
+                output: "dir ".into(),
+                line: 2
+            }),
+            Transcript::parse("$ ls\ndir ").err()
+        );
+        assert_eq!(
+            Some(Error::Conflict {
+                name: "b".into(),
+                line: 5
+            }),
+            Transcript::parse("$ ls\n12 b\n$ cd /\n$ ls\n13 b")
+                .unwrap()
+                .replay()
+                .err()
+        );
+        assert_eq!(
+            Some(Error::Conflict {
+                name: "a".into(),
+                line: 4
+            }),
+            Transcript::parse("$ cd a\n$ cd /\n$ ls\n12 a")
+                .unwrap()
+                .replay()
+                .err()
+        );
+    }
+}
@@ -1,5 +1,20 @@
-use anyhow::{Context, Error, Result};
+use common::Solver;
 use std::cmp;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("row {row} has {got} trees, while the rows before it have {expected}")]
+    RaggedRow {
+        expected: usize,
+        got: usize,
+        row: usize,
+    },
+    #[error("row {row}, column {col}: {ch:?} is not a tree height")]
+    InvalidHeight { ch: char, row: usize, col: usize },
+    #[error("({x}, {y}) is outside the grid")]
+    OutOfBounds { x: usize, y: usize },
+}
 
 pub enum Direction {
     Top,
@@ -12,33 +27,39 @@ pub enum Direction {
 pub struct Grid(Vec<Vec<u32>>);
 
 impl Grid {
-    pub fn add_row(&mut self, row: &str) -> Result<()> {
+    pub fn add_row(&mut self, row: &str) -> Result<(), Error> {
+        // Rows and columns are reported 1-based
+        let row_number = self.height() + 1;
         let current_width = self.width();
-        if current_width > 0 && current_width != row.len() {
-            return Err(Error::msg(format!(
-                "row to be added is of width {}, while existing grid is of width {}",
-                row.len(),
-                current_width
-            )));
+        let width = row.chars().count();
+        if current_width > 0 && current_width != width {
+            return Err(Error::RaggedRow {
+                expected: current_width,
+                got: width,
+                row: row_number,
+            });
         }
 
         self.0.push(
-            row.chars()
-                .map(|c| {
-                    c.to_digit(10)
-                        .context("converting tree height char to digit")
+            (1..)
+                .zip(row.chars())
+                .map(|(col, ch)| {
+                    ch.to_digit(10).ok_or(Error::InvalidHeight {
+                        ch,
+                        row: row_number,
+                        col,
+                    })
                 })
                 .collect::<Result<Vec<u32>, Error>>()?,
         );
         Ok(())
     }
 
-    pub fn get(&self, x: usize, y: usize) -> Result<&u32> {
+    pub fn get(&self, x: usize, y: usize) -> Result<&u32, Error> {
         self.0
             .get(y)
-            .context("getting row")?
-            .get(x)
-            .context("getting tree from row")
+            .and_then(|row| row.get(x))
+            .ok_or(Error::OutOfBounds { x, y })
     }
 
     pub fn height(&self) -> usize {
@@ -46,62 +67,51 @@ impl Grid {
     }
 
     pub fn width(&self) -> usize {
-        match self.0.get(0) {
+        match self.0.first() {
             Some(row) => row.len(),
             None => 0,
         }
     }
 
-    pub fn is_visible(&self, x: usize, y: usize) -> Result<bool> {
+    pub fn is_visible(&self, x: usize, y: usize) -> Result<bool, Error> {
         Ok(self.is_visible_from(x, y, &Direction::Top)?
             || self.is_visible_from(x, y, &Direction::Right)?
             || self.is_visible_from(x, y, &Direction::Bottom)?
             || self.is_visible_from(x, y, &Direction::Left)?)
     }
 
-    pub fn is_visible_from(&self, x: usize, y: usize, direction: &Direction) -> Result<bool> {
-        let target_height = self.get(x, y).context("get target tree height")?;
+    pub fn is_visible_from(
+        &self,
+        x: usize,
+        y: usize,
+        direction: &Direction,
+    ) -> Result<bool, Error> {
+        let target_height = self.get(x, y)?;
         match direction {
             Direction::Top => {
                 for i in 0..y {
-                    if self
-                        .get(x, i)
-                        .context("get tree height, checking visibility from top")?
-                        >= target_height
-                    {
+                    if self.get(x, i)? >= target_height {
                         return Ok(false);
                     }
                 }
             }
             Direction::Right => {
                 for i in x + 1..self.width() {
-                    if self
-                        .get(i, y)
-                        .context("get tree height, checking visibility from right")?
-                        >= target_height
-                    {
+                    if self.get(i, y)? >= target_height {
                         return Ok(false);
                     }
                 }
             }
             Direction::Bottom => {
                 for i in y + 1..self.height() {
-                    if self
-                        .get(x, i)
-                        .context("get tree height, checking visibility from bottom")?
-                        >= target_height
-                    {
+                    if self.get(x, i)? >= target_height {
                         return Ok(false);
                     }
                 }
             }
             Direction::Left => {
                 for i in 0..x {
-                    if self
-                        .get(i, y)
-                        .context("get tree height, checking visibility from left")?
-                        >= target_height
-                    {
+                    if self.get(i, y)? >= target_height {
                         return Ok(false);
                     }
                 }
@@ -142,25 +152,26 @@ impl Grid {
         best
     }
 
-    pub fn scenic_score(&self, x: usize, y: usize) -> Result<u32> {
+    pub fn scenic_score(&self, x: usize, y: usize) -> Result<u32, Error> {
         Ok(self.viewing_distance(x, y, &Direction::Top)?
             * self.viewing_distance(x, y, &Direction::Right)?
             * self.viewing_distance(x, y, &Direction::Bottom)?
             * self.viewing_distance(x, y, &Direction::Left)?)
     }
 
-    pub fn viewing_distance(&self, x: usize, y: usize, direction: &Direction) -> Result<u32> {
-        let target_height = self.get(x, y).context("get target tree height")?;
+    pub fn viewing_distance(
+        &self,
+        x: usize,
+        y: usize,
+        direction: &Direction,
+    ) -> Result<u32, Error> {
+        let target_height = self.get(x, y)?;
         let mut distance = 0;
         match direction {
             Direction::Top => {
                 for i in (0..y).rev() {
                     distance += 1;
-                    if self
-                        .get(x, i)
-                        .context("get tree height, checking top viewing distance")?
-                        >= target_height
-                    {
+                    if self.get(x, i)? >= target_height {
                         break;
                     }
                 }
@@ -168,11 +179,7 @@ impl Grid {
             Direction::Right => {
                 for i in x + 1..self.width() {
                     distance += 1;
-                    if self
-                        .get(i, y)
-                        .context("get tree height, checking top viewing distance")?
-                        >= target_height
-                    {
+                    if self.get(i, y)? >= target_height {
                         break;
                     }
                 }
@@ -180,11 +187,7 @@ impl Grid {
             Direction::Bottom => {
                 for i in y + 1..self.height() {
                     distance += 1;
-                    if self
-                        .get(x, i)
-                        .context("get tree height, checking top viewing distance")?
-                        >= target_height
-                    {
+                    if self.get(x, i)? >= target_height {
                         break;
                     }
                 }
@@ -192,11 +195,7 @@ impl Grid {
             Direction::Left => {
                 for i in (0..x).rev() {
                     distance += 1;
-                    if self
-                        .get(i, y)
-                        .context("get tree height, checking top viewing distance")?
-                        >= target_height
-                    {
+                    if self.get(i, y)? >= target_height {
                         break;
                     }
                 }
@@ -218,3 +217,68 @@ impl Grid {
         }
     }
 }
+
+impl TryFrom<&str> for Grid {
+    type Error = Error;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let mut grid = Grid::default();
+        for line in input.lines() {
+            grid.add_row(line)?;
+        }
+        Ok(grid)
+    }
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn part1(input: &str) -> anyhow::Result<usize> {
+        Ok(Grid::try_from(input)?.count_visible())
+    }
+
+    fn part2(input: &str) -> anyhow::Result<u32> {
+        Ok(Grid::try_from(input)?.best_scenic_score())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "30373\n25512\n65332\n33549\n35390";
+
+    #[test]
+    fn example() {
+        let grid = Grid::try_from(EXAMPLE).unwrap();
+        assert_eq!(21, grid.count_visible());
+        assert_eq!(8, grid.best_scenic_score());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Some(Error::RaggedRow {
+                expected: 3,
+                got: 2,
+                row: 2
+            }),
+            Grid::try_from("303\n25").err()
+        );
+        assert_eq!(
+            Some(Error::InvalidHeight {
+                ch: 'x',
+                row: 2,
+                col: 2
+            }),
+            Grid::try_from("303\n2x5").err()
+        );
+        assert_eq!(
+            Some(Error::OutOfBounds { x: 3, y: 0 }),
+            Grid::try_from("303").unwrap().get(3, 0).err()
+        );
+    }
+}
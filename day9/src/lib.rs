@@ -1,12 +1,40 @@
-use anyhow::{Context, Error, Result};
+use common::Solver;
+use thiserror::Error;
 
 use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("cannot create a rope of length < 1")]
+    ZeroLengthRope,
+    #[error("expected a motion of the form `D N`, got {motion:?}")]
+    NotAMotion { motion: String },
+    #[error("{direction:?} is not a valid direction")]
+    NotADirection { direction: String },
+    #[error("{token:?} is not a valid number of steps")]
+    NotACount { token: String },
+    #[error("line {line}: expected a motion of the form `D N`, got {motion:?}")]
+    MalformedMotion { motion: String, line: usize },
+    #[error("line {line}: {direction:?} is not a valid direction")]
+    InvalidDirection { direction: String, line: usize },
+    #[error("line {line}: {token:?} is not a valid number of steps")]
+    InvalidCount { token: String, line: usize },
+    #[error("line {line}: failed to read input: {kind}")]
+    Io { kind: io::ErrorKind, line: usize },
+}
 
 pub struct Knot {
     pub position: (i32, i32),
     pub next: Option<Box<Knot>>,
 }
 
+impl Default for Knot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Knot {
     pub fn new() -> Self {
         Self {
@@ -24,7 +52,7 @@ impl Knot {
             return;
         }
 
-        let mut next = self.next.as_mut().expect("guaranteed to be Some");
+        let next = self.next.as_mut().expect("guaranteed to be Some");
 
         let x_delta = self.position.0 - next.position.0;
         let y_delta = self.position.1 - next.position.1;
@@ -68,9 +96,9 @@ pub struct Puzzle {
 }
 
 impl Puzzle {
-    pub fn new(rope_length: usize) -> Result<Self> {
+    pub fn new(rope_length: usize) -> Result<Self, Error> {
         if rope_length < 1 {
-            return Err(Error::msg("cannot create a rope of length < 1"));
+            return Err(Error::ZeroLengthRope);
         }
         let mut puzzle = Self {
             rope: Knot::new(),
@@ -114,32 +142,129 @@ pub struct Motion {
     count: u32,
 }
 
+impl Motion {
+    fn parse(value: &str, line: usize) -> Result<Self, Error> {
+        Self::try_from(value).map_err(|e| match e {
+            Error::NotAMotion { motion } => Error::MalformedMotion { motion, line },
+            Error::NotADirection { direction } => Error::InvalidDirection { direction, line },
+            Error::NotACount { token } => Error::InvalidCount { token, line },
+            e => e,
+        })
+    }
+
+    /// Reads one motion per line, so only the current line is held in memory.
+    pub fn iter_from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Self, Error>> {
+        (1..).zip(reader.lines()).map(|(line, motion)| {
+            let motion = motion.map_err(|e| Error::Io {
+                kind: e.kind(),
+                line,
+            })?;
+            Self::parse(&motion, line)
+        })
+    }
+}
+
 impl TryFrom<&str> for Motion {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let (direction, count) = value.split_once(' ').context("splitting motion str")?;
-        let count = count
-            .parse()
-            .context("parsing number of time to apply motion")?;
-        match direction {
-            "U" => Ok(Motion {
-                direction: Direction::Up,
-                count,
+        let (direction, count) = value.split_once(' ').ok_or_else(|| Error::NotAMotion {
+            motion: value.into(),
+        })?;
+        let count = count.parse().map_err(|_| Error::NotACount {
+            token: count.into(),
+        })?;
+        let direction = match direction {
+            "U" => Direction::Up,
+            "R" => Direction::Right,
+            "D" => Direction::Down,
+            "L" => Direction::Left,
+            direction => {
+                return Err(Error::NotADirection {
+                    direction: direction.into(),
+                })
+            }
+        };
+        Ok(Motion { direction, count })
+    }
+}
+
+fn unique_tail_locations(input: &str, rope_length: usize) -> Result<usize, Error> {
+    let mut puzzle = Puzzle::new(rope_length)?;
+    for motion in Motion::iter_from_reader(input.as_bytes()) {
+        puzzle.apply_motion(&motion?);
+    }
+    Ok(puzzle.num_unique_tail_locations())
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn part1(input: &str) -> anyhow::Result<usize> {
+        Ok(unique_tail_locations(input, 2)?)
+    }
+
+    fn part2(input: &str) -> anyhow::Result<usize> {
+        Ok(unique_tail_locations(input, 10)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
+        assert_eq!(Ok(13), unique_tail_locations(input, 2));
+        assert_eq!(Ok(1), unique_tail_locations(input, 10));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Some(Error::ZeroLengthRope), Puzzle::new(0).err());
+        assert_eq!(
+            Err(Error::InvalidDirection {
+                direction: "X".into(),
+                line: 2
             }),
-            "R" => Ok(Motion {
-                direction: Direction::Right,
-                count,
+            unique_tail_locations("R 4\nX 4", 2)
+        );
+        assert_eq!(
+            Err(Error::InvalidCount {
+                token: "-1".into(),
+                line: 1
             }),
-            "D" => Ok(Motion {
-                direction: Direction::Down,
-                count,
+            unique_tail_locations("R -1", 2)
+        );
+        assert_eq!(
+            Err(Error::MalformedMotion {
+                motion: "R4".into(),
+                line: 1
             }),
-            "L" => Ok(Motion {
-                direction: Direction::Left,
-                count,
+            unique_tail_locations("R4", 2)
+        );
+        assert_eq!(
+            Some(Error::Io {
+                kind: io::ErrorKind::InvalidData,
+                line: 2
             }),
-            direction @ _ => Err(Error::msg(format!("{direction} is not a valid direction"))),
-        }
+            Motion::iter_from_reader(&b"R 4\n\xff\n"[..]).find_map(Result::err)
+        );
+        assert_eq!(
+            Some(Error::NotADirection {
+                direction: "X".into()
+            }),
+            Motion::try_from("X 4").err()
+        );
+        assert_eq!(
+            Some(Error::NotAMotion {
+                motion: "R4".into()
+            }),
+            Motion::try_from("R4").err()
+        );
     }
 }
@@ -1,23 +1,25 @@
-use anyhow::{Context, Result};
+use std::io;
 
-use std::io::{self, Read};
-
-use day9::{Puzzle, Motion};
-
-const ROPE_LENGTH: usize = 10;
+use anyhow::Result;
+use day9::{Motion, Puzzle};
 
 fn main() -> Result<()> {
-    let mut buffer = String::new();
-    let mut stdin = io::stdin().lock();
-    stdin.read_to_string(&mut buffer).context("read stdin")?;
-
-    let mut puzzle = Puzzle::new(ROPE_LENGTH).context("creating puzzle")?;
-    for line in buffer.lines() {
-        let motion = Motion::try_from(line).context("parsing line as motion")?;
-        puzzle.apply_motion(&motion);
+    let mut short_rope = Puzzle::new(2)?;
+    let mut long_rope = Puzzle::new(10)?;
+    for motion in Motion::iter_from_reader(io::stdin().lock()) {
+        let motion = motion?;
+        short_rope.apply_motion(&motion);
+        long_rope.apply_motion(&motion);
     }
 
-    println!("Tail has visited {} unique locations", puzzle.num_unique_tail_locations());
+    println!(
+        "Tail of a 2 knot rope has visited {} unique locations",
+        short_rope.num_unique_tail_locations()
+    );
+    println!(
+        "Tail of a 10 knot rope has visited {} unique locations",
+        long_rope.num_unique_tail_locations()
+    );
 
     Ok(())
 }
@@ -0,0 +1,163 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Error, Result};
+use common::{Solver, Unsolved};
+
+const DAYS: u8 = 16;
+const DEFAULT_INPUTS_DIRECTORY: &str = "inputs";
+const USAGE: &str = "\
+Usage:
+    aoc run <day> <part> [--input FILE]
+    aoc run all [--inputs DIRECTORY]
+
+<day> is 1-16 and <part> is 1, 2 or all. Input is read from stdin unless --input is given.
+When running every day, input for day N is read from DIRECTORY/dayN.txt (default: inputs).";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Part {
+    One,
+    Two,
+}
+
+impl TryFrom<&str> for Part {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            part => Err(Error::msg(format!("{part} is not a valid part"))),
+        }
+    }
+}
+
+impl Part {
+    fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+fn parse_day(value: &str) -> Result<u8> {
+    let day = value
+        .parse()
+        .with_context(|| format!("{value} is not a valid day"))?;
+    if !(1..=DAYS).contains(&day) {
+        return Err(Error::msg(format!("day must be between 1 and {DAYS}")));
+    }
+    Ok(day)
+}
+
+fn solve_part<S: Solver>(part: Part, input: &str) -> Result<String> {
+    match part {
+        Part::One => S::part1(input).map(|answer| answer.to_string()),
+        Part::Two => S::part2(input).map(|answer| answer.to_string()),
+    }
+}
+
+fn solve(day: u8, part: Part, input: &str) -> Result<String> {
+    match day {
+        1 => solve_part::<day1::Day1>(part, input),
+        2 => solve_part::<day2::Day2>(part, input),
+        3 => solve_part::<day3::Day3>(part, input),
+        4 => solve_part::<day4::Day4>(part, input),
+        5 => solve_part::<day5::Day5>(part, input),
+        6 => solve_part::<day6::Day6>(part, input),
+        7 => solve_part::<day7::Day7>(part, input),
+        8 => solve_part::<day8::Day8>(part, input),
+        9 => solve_part::<day9::Day9>(part, input),
+        10 => solve_part::<day10::Day10>(part, input),
+        11 => solve_part::<day11::Day11>(part, input),
+        12 => solve_part::<day12::Day12>(part, input),
+        13 => solve_part::<day13::Day13>(part, input),
+        14 => solve_part::<day14::Day14>(part, input),
+        15 => solve_part::<day15::Day15>(part, input),
+        16 => solve_part::<day16::Day16>(part, input),
+        day => Err(Error::msg(format!("day {day} has not been solved"))),
+    }
+}
+
+fn run(day: u8, part: Part, input: &str) -> Result<Duration> {
+    let start = Instant::now();
+    let answer = solve(day, part, input);
+    let elapsed = start.elapsed();
+
+    let label = format!("Day {day} part {}", part.number());
+    match answer {
+        // Multi-line answers, such as rendered screens, start on their own line
+        Ok(answer) if answer.contains('\n') => {
+            println!("{label} ({elapsed:.2?}):\n{}", answer.trim_end())
+        }
+        Ok(answer) => println!("{label}: {answer} ({elapsed:.2?})"),
+        Err(e) => return Err(e.context(label)),
+    }
+    Ok(elapsed)
+}
+
+fn run_day(day: u8, parts: &[Part], input: &str) -> Result<()> {
+    for part in parts {
+        run(day, *part, input)?;
+    }
+    Ok(())
+}
+
+fn run_all(inputs: &Path) -> Result<()> {
+    let mut total = Duration::ZERO;
+    let mut failures = 0;
+    for day in 1..=DAYS {
+        let path = inputs.join(format!("day{day}.txt"));
+        let Ok(input) = fs::read_to_string(&path) else {
+            println!("Day {day}: skipped, no input at {}", path.display());
+            continue;
+        };
+        for part in [Part::One, Part::Two] {
+            match run(day, part, &input) {
+                Ok(elapsed) => total += elapsed,
+                Err(e) if e.is::<Unsolved>() => {
+                    println!("Day {day} part {}: skipped, not solved yet", part.number());
+                }
+                Err(e) => {
+                    failures += 1;
+                    println!("{e:#}");
+                }
+            }
+        }
+    }
+    println!("Total time: {total:.2?}");
+
+    if failures > 0 {
+        return Err(Error::msg(format!("{failures} part(s) failed")));
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["run", "all"] => run_all(Path::new(DEFAULT_INPUTS_DIRECTORY)),
+        ["run", "all", "--inputs", directory] => run_all(&PathBuf::from(directory)),
+        ["run", day, part, rest @ ..] => {
+            let day = parse_day(day)?;
+            let parts = match *part {
+                "all" => vec![Part::One, Part::Two],
+                part => vec![Part::try_from(part)?],
+            };
+            let input = match rest {
+                [] => common::read_stdin()?,
+                ["--input", file] => fs::read_to_string(file)
+                    .with_context(|| format!("reading input from {file}"))?,
+                _ => return Err(Error::msg(USAGE)),
+            };
+            run_day(day, &parts, &input)
+        }
+        _ => Err(Error::msg(USAGE)),
+    }
+}